@@ -0,0 +1,92 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_json::json;
+use soupheatmap::json_processor::load_json_files_with_progress;
+use std::fs;
+use std::path::Path;
+
+const MATCH_COUNT: usize = 50;
+
+/// Build a match file shaped like a VCT export, with enough rounds to make parsing non-trivial
+fn synthetic_match(match_id: &str) -> serde_json::Value {
+    let players: Vec<_> = (0..10)
+        .map(|i| {
+            json!({
+                "puuid": format!("player-{}", i),
+                "gameName": format!("Player{}", i),
+                "tagLine": "BENCH",
+                "characterId": null,
+                "teamId": if i < 5 { "Blue" } else { "Red" },
+                "stats": { "score": 0, "kills": 0, "deaths": 0, "assists": 0, "roundsPlayed": 24 }
+            })
+        })
+        .collect();
+
+    let player_locations: Vec<_> = (0..10)
+        .map(|i| json!({ "puuid": format!("player-{}", i), "location": { "x": i * 100, "y": i * 50 } }))
+        .collect();
+
+    let rounds: Vec<_> = (0..24)
+        .map(|round| {
+            let kills: Vec<_> = (0..5)
+                .map(|k| {
+                    json!({
+                        "killer": format!("player-{}", k),
+                        "victim": format!("player-{}", k + 5),
+                        "finishingDamage": { "damageItem": "EE8E8D15-496B-07AC-E5F6-8FAE5D4C7B1A" },
+                        "victimLocation": { "x": round * 10, "y": k * 10 },
+                        "timeSinceRoundStartMillis": k * 5000,
+                        "playerLocations": player_locations
+                    })
+                })
+                .collect();
+
+            json!({
+                "roundNum": round,
+                "winningTeam": if round % 2 == 0 { "Blue" } else { "Red" },
+                "playerStats": [{ "puuid": "player-0", "kills": kills }]
+            })
+        })
+        .collect();
+
+    json!({
+        "matchInfo": {
+            "matchId": match_id,
+            "map": "/Game/Maps/Ascent/Ascent",
+            "gameStartMillis": 1_700_000_000_000i64,
+            "gameLengthMillis": 2_400_000
+        },
+        "players": players,
+        "roundResults": rounds
+    })
+}
+
+fn write_dataset(dir: &Path) {
+    for i in 0..MATCH_COUNT {
+        let match_id = format!("bench-match-{}", i);
+        let contents = serde_json::to_string(&synthetic_match(&match_id)).unwrap();
+        fs::write(dir.join(format!("{}.json", match_id)), contents).unwrap();
+    }
+}
+
+fn bench_load(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    write_dataset(dir.path());
+    let folder = dir.path().to_str().unwrap().to_string();
+
+    let mut group = c.benchmark_group("load_json_files_with_progress");
+
+    // A single-threaded pool runs the same code path sequentially
+    let sequential_pool = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    group.bench_function("sequential", |b| {
+        b.iter(|| sequential_pool.install(|| load_json_files_with_progress(&folder, false, |_, _| {}).unwrap()))
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| load_json_files_with_progress(&folder, false, |_, _| {}).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_load);
+criterion_main!(benches);
@@ -0,0 +1,155 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde_json::json;
+use soupheatmap::heatmap::compute_heatmap;
+use soupheatmap::json_processor::{
+    clear_match_cache, extract_kill_events, get_match_by_id, load_json_files, load_json_files_with_progress,
+    parse_match_detail
+};
+use soupheatmap::models::{LoadOptions, VctMatchData};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const MATCH_COUNT: usize = 100;
+
+/// Build a match file shaped like a VCT export, as JSON text
+///
+/// Players alternate between Blue and Red, and every round has one kill per
+/// player pair with every player's position attached, so round count drives
+/// both parse and kill extraction cost.
+fn generate_synthetic_vct_json(n_rounds: u32, n_players: u32) -> String {
+    generate_match(&format!("bench-{}r-{}p", n_rounds, n_players), n_rounds, n_players)
+}
+
+fn generate_match(match_id: &str, n_rounds: u32, n_players: u32) -> String {
+    let team = |i: u32| if i.is_multiple_of(2) { "Blue" } else { "Red" };
+
+    let players: Vec<_> = (0..n_players)
+        .map(|i| {
+            json!({
+                "puuid": format!("player-{}", i),
+                "gameName": format!("Player{}", i),
+                "tagLine": "BENCH",
+                "characterId": null,
+                "teamId": team(i),
+                "stats": { "score": 0, "kills": 0, "deaths": 0, "assists": 0, "roundsPlayed": n_rounds }
+            })
+        })
+        .collect();
+
+    let player_locations: Vec<_> = (0..n_players)
+        .map(|i| json!({ "puuid": format!("player-{}", i), "location": { "x": 1000 + i as i32 * 100, "y": -3000 + i as i32 * 50 } }))
+        .collect();
+
+    let rounds: Vec<_> = (0..n_rounds)
+        .map(|round| {
+            let kills: Vec<_> = (0..n_players / 2)
+                .map(|k| {
+                    json!({
+                        "killer": format!("player-{}", k * 2),
+                        "victim": format!("player-{}", k * 2 + 1),
+                        "finishingDamage": { "damageItem": "EE8E8D15-496B-07AC-E5F6-8FAE5D4C7B1A" },
+                        "victimLocation": { "x": 1000 + (round % 50) as i32 * 100, "y": -3000 + k as i32 * 200 },
+                        "timeSinceRoundStartMillis": k * 5000,
+                        "playerLocations": player_locations
+                    })
+                })
+                .collect();
+
+            json!({
+                "roundNum": round,
+                "winningTeam": team(round),
+                "playerStats": [{ "puuid": "player-0", "kills": kills }]
+            })
+        })
+        .collect();
+
+    json!({
+        "matchInfo": {
+            "matchId": match_id,
+            "map": "/Game/Maps/Ascent/Ascent",
+            "gameStartMillis": 1_700_000_000_000i64,
+            "gameLengthMillis": 2_400_000
+        },
+        "players": players,
+        "roundResults": rounds
+    })
+    .to_string()
+}
+
+fn write_dataset(dir: &Path) {
+    for i in 0..MATCH_COUNT {
+        let match_id = format!("bench-match-{}", i);
+        fs::write(dir.join(format!("{}.json", match_id)), generate_match(&match_id, 24, 10)).unwrap();
+    }
+}
+
+fn bench_load(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    write_dataset(dir.path());
+    let folder = dir.path().to_str().unwrap().to_string();
+
+    c.bench_function("load_json_files_with_progress/100_files", |b| {
+        b.iter(|| load_json_files_with_progress(&folder, false, |_, _| {}).unwrap())
+    });
+}
+
+fn bench_extract_kill_events(c: &mut Criterion) {
+    let data: VctMatchData = serde_json::from_str(&generate_synthetic_vct_json(200, 10)).unwrap();
+    let player_teams: HashMap<&str, &str> = data.players
+        .iter()
+        .map(|p| (p.puuid.as_str(), p.team_id.as_str()))
+        .collect();
+
+    c.bench_function("extract_kill_events/200_rounds", |b| {
+        b.iter(|| extract_kill_events(&data.round_results, &player_teams))
+    });
+}
+
+fn bench_compute_heatmap(c: &mut Criterion) {
+    let data: VctMatchData = serde_json::from_str(&generate_synthetic_vct_json(200, 10)).unwrap();
+    let detail = parse_match_detail(Path::new("bench.json"), &data);
+
+    c.bench_function("compute_heatmap/512x512", |b| {
+        b.iter(|| compute_heatmap(&detail.map_display, &detail.kill_events, 512, 512))
+    });
+}
+
+fn bench_get_match_by_id(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    write_dataset(dir.path());
+    let folder = dir.path().to_str().unwrap().to_string();
+    let match_id = format!("bench-match-{}", MATCH_COUNT - 1);
+
+    let mut group = c.benchmark_group("get_match_by_id");
+
+    // Index built for the folder, so the lookup reads a single file
+    load_json_files(&folder, None, &LoadOptions::default()).unwrap();
+    group.bench_with_input(BenchmarkId::new("indexed", MATCH_COUNT), &match_id, |b, match_id| {
+        b.iter(|| {
+            clear_match_cache();
+            get_match_by_id(Some(&folder), match_id).unwrap()
+        })
+    });
+
+    // Index built for some other folder, so the lookup falls back to walking this one
+    let other = tempfile::tempdir().unwrap();
+    load_json_files(other.path().to_str().unwrap(), None, &LoadOptions::default()).unwrap();
+    group.bench_with_input(BenchmarkId::new("scan", MATCH_COUNT), &match_id, |b, match_id| {
+        b.iter(|| {
+            clear_match_cache();
+            get_match_by_id(Some(&folder), match_id).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_load,
+    bench_extract_kill_events,
+    bench_compute_heatmap,
+    bench_get_match_by_id
+);
+criterion_main!(benches);
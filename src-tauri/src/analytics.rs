@@ -0,0 +1,999 @@
+use crate::coordinates;
+use crate::error::ProcessorError;
+use crate::json_processor;
+use crate::models::*;
+use std::collections::HashMap;
+
+/// Iteration cap for k-means when the caller has no preference
+pub const KMEANS_MAX_ITER: u32 = 100;
+
+/// Victim positions in minimap space, paired with the index of their kill event
+fn victim_uvs(map_display: &str, events: &[KillEvent]) -> Vec<(usize, (f64, f64))> {
+    events
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| coordinates::to_uv(&e.victim_location, map_display).map(|uv| (i, uv)))
+        .collect()
+}
+
+fn distance_sq(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+    dx * dx + dy * dy
+}
+
+/// Group kill victim positions into `k` hot zones using Lloyd's algorithm
+///
+/// Centroids start at evenly spaced kills so results are deterministic.
+/// Kills without a usable position are ignored, and clusters that end up
+/// empty are dropped, so fewer than `k` clusters may be returned.
+pub fn cluster_kill_locations(map_display: &str, events: &[KillEvent], k: u32, max_iter: u32) -> Vec<KillCluster> {
+    let points = victim_uvs(map_display, events);
+    if points.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let k = (k as usize).min(points.len());
+    let mut centroids: Vec<(f64, f64)> = (0..k)
+        .map(|i| points[i * points.len() / k].1)
+        .collect();
+    let mut assignments = vec![usize::MAX; points.len()];
+
+    for _ in 0..max_iter.max(1) {
+        // Assign each point to its nearest centroid
+        let mut changed = false;
+        for (slot, (_, uv)) in assignments.iter_mut().zip(&points) {
+            let nearest = centroids
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| distance_sq(*uv, **a).total_cmp(&distance_sq(*uv, **b)))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+
+            if *slot != nearest {
+                *slot = nearest;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+
+        // Move each centroid to the mean of its members; empty clusters stay put
+        let mut sums = vec![(0.0, 0.0, 0u32); k];
+        for (&cluster, (_, uv)) in assignments.iter().zip(&points) {
+            sums[cluster].0 += uv.0;
+            sums[cluster].1 += uv.1;
+            sums[cluster].2 += 1;
+        }
+        for (centroid, (sum_u, sum_v, count)) in centroids.iter_mut().zip(sums) {
+            if count > 0 {
+                *centroid = (sum_u / count as f64, sum_v / count as f64);
+            }
+        }
+    }
+
+    centroids
+        .into_iter()
+        .enumerate()
+        .filter_map(|(cluster, centroid_uv)| {
+            let mut member_count = 0;
+            let mut weapons: HashMap<&str, u32> = HashMap::new();
+
+            for (&assigned, (event_index, _)) in assignments.iter().zip(&points) {
+                if assigned != cluster {
+                    continue;
+                }
+                member_count += 1;
+                if let Some(weapon) = events[*event_index].weapon.as_deref() {
+                    *weapons.entry(weapon).or_insert(0) += 1;
+                }
+            }
+
+            // Most common weapon, ties broken alphabetically
+            let representative_weapon = weapons
+                .into_iter()
+                .max_by(|(a_name, a_count), (b_name, b_count)| a_count.cmp(b_count).then(b_name.cmp(a_name)))
+                .map(|(name, _)| name.to_string());
+
+            (member_count > 0).then_some(KillCluster {
+                centroid_uv,
+                member_count,
+                representative_weapon,
+            })
+        })
+        .collect()
+}
+
+/// Find dense groups of kills and the outliers around them with DBSCAN
+///
+/// `epsilon` is a radius in normalized minimap units. Each cluster lists every
+/// member (core and border kills) in `core_members`; noise kills are attached
+/// as `outliers` to the cluster with the closest member. If nothing is dense
+/// enough to form a cluster, a single entry holds all positioned kills as
+/// outliers. Indices refer to `events`, and kills without a usable position
+/// appear nowhere.
+pub fn dbscan_kill_locations(map_display: &str, events: &[KillEvent], epsilon: f64, min_points: usize) -> Vec<SpatialCluster> {
+    let points = victim_uvs(map_display, events);
+    let eps_sq = epsilon * epsilon;
+
+    // O(n²) neighbourhoods are fine for a few hundred kills
+    let neighbours: Vec<Vec<usize>> = points
+        .iter()
+        .map(|(_, a)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, b))| distance_sq(*a, *b) <= eps_sq)
+                .map(|(j, _)| j)
+                .collect()
+        })
+        .collect();
+    let is_core = |i: usize| neighbours[i].len() >= min_points.max(1);
+
+    let mut labels: Vec<Option<usize>> = vec![None; points.len()];
+    let mut cluster_count = 0;
+
+    for start in 0..points.len() {
+        if labels[start].is_some() || !is_core(start) {
+            continue;
+        }
+
+        let cluster = cluster_count;
+        cluster_count += 1;
+        labels[start] = Some(cluster);
+
+        // Expand through density-reachable points; only core points spread further
+        let mut frontier = vec![start];
+        while let Some(i) = frontier.pop() {
+            if !is_core(i) {
+                continue;
+            }
+            for &j in &neighbours[i] {
+                if labels[j].is_none() {
+                    labels[j] = Some(cluster);
+                    frontier.push(j);
+                }
+            }
+        }
+    }
+
+    let noise: Vec<usize> = (0..points.len()).filter(|&i| labels[i].is_none()).collect();
+
+    if cluster_count == 0 {
+        if noise.is_empty() {
+            return Vec::new();
+        }
+        return vec![SpatialCluster {
+            core_members: Vec::new(),
+            outliers: noise.iter().map(|&i| points[i].0).collect(),
+        }];
+    }
+
+    let mut clusters = vec![SpatialCluster { core_members: Vec::new(), outliers: Vec::new() }; cluster_count];
+    for (i, label) in labels.iter().enumerate() {
+        if let Some(cluster) = label {
+            clusters[*cluster].core_members.push(points[i].0);
+        }
+    }
+
+    for i in noise {
+        let nearest = labels
+            .iter()
+            .enumerate()
+            .filter_map(|(j, label)| label.map(|cluster| (cluster, distance_sq(points[i].1, points[j].1))))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(cluster, _)| cluster);
+
+        if let Some(cluster) = nearest {
+            clusters[cluster].outliers.push(points[i].0);
+        }
+    }
+
+    clusters
+}
+
+/// Killer-to-victim segments in minimap space for drawing flow maps
+///
+/// Kills where either position is missing or unusable are skipped.
+pub fn kill_trajectories(events: &[KillEvent], map: &str) -> Vec<TrajectoryLine> {
+    events
+        .iter()
+        .filter_map(|e| {
+            let start = coordinates::to_uv(e.killer_location.as_ref()?, map)?;
+            let end = coordinates::to_uv(&e.victim_location, map)?;
+            Some(TrajectoryLine {
+                start,
+                end,
+                weapon: e.weapon.clone(),
+                round_num: e.round_num,
+            })
+        })
+        .collect()
+}
+
+/// Largest possible distance between two points in minimap space
+const MAX_UV_DISTANCE: f64 = std::f64::consts::SQRT_2;
+
+/// Histogram of killer-to-victim distances for one weapon
+///
+/// Buckets evenly split the full [0, √2] range of minimap distances so
+/// histograms for different weapons can be compared directly. The weapon name
+/// is matched case-insensitively; kills missing either position are skipped.
+pub fn kill_distance_distribution(events: &[KillEvent], map: &str, weapon: &str, bucket_count: u32) -> DistributionHistogram {
+    let bucket_count = bucket_count as usize;
+    if bucket_count == 0 {
+        return DistributionHistogram { bucket_edges_uv: Vec::new(), counts: Vec::new() };
+    }
+
+    let width = MAX_UV_DISTANCE / bucket_count as f64;
+    let mut histogram = DistributionHistogram {
+        bucket_edges_uv: (0..=bucket_count).map(|i| i as f64 * width).collect(),
+        counts: vec![0; bucket_count],
+    };
+
+    for e in events {
+        if !e.weapon.as_deref().is_some_and(|w| w.eq_ignore_ascii_case(weapon)) {
+            continue;
+        }
+
+        let (Some(killer), Some(victim)) = (
+            e.killer_location.as_ref().and_then(|l| coordinates::to_uv(l, map)),
+            coordinates::to_uv(&e.victim_location, map),
+        ) else {
+            continue;
+        };
+
+        let distance = distance_sq(killer, victim).sqrt();
+        let bucket = ((distance / width) as usize).min(bucket_count - 1);
+        histogram.counts[bucket] += 1;
+    }
+
+    histogram
+}
+
+/// Histogram of killer-to-victim distances in game units, as `(bucket_start, count)` pairs
+///
+/// Buckets run contiguously from 0 to the longest kill so empty ranges show up as zero.
+/// Kills with an unreliable distance are skipped, and a non-positive bucket size gives
+/// an empty histogram.
+pub fn distance_histogram(events: &[KillEvent], bucket: f64) -> Vec<(f64, u32)> {
+    if bucket <= 0.0 {
+        return Vec::new();
+    }
+
+    let buckets: Vec<usize> = events
+        .iter()
+        .filter(|e| e.distance_reliable)
+        .map(|e| (e.distance / bucket) as usize)
+        .collect();
+
+    let Some(&last) = buckets.iter().max() else {
+        return Vec::new();
+    };
+
+    let mut counts = vec![0u32; last + 1];
+    for i in buckets {
+        counts[i] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| (i as f64 * bucket, count))
+        .collect()
+}
+
+/// Players as nodes and killer-to-victim counts as edges, aggregated over all rounds
+///
+/// Observers are left out of the nodes. Edges are sorted by killer then victim.
+pub fn match_kill_graph(detail: &MatchDetail) -> KillGraph {
+    let nodes = detail.players
+        .iter()
+        .filter(|p| !p.is_observer)
+        .map(|p| PlayerNode {
+            puuid: p.puuid.clone(),
+            game_name: p.game_name.clone(),
+            team: p.team.clone(),
+        })
+        .collect();
+
+    let mut counts: HashMap<(&str, &str), u32> = HashMap::new();
+    for e in &detail.kill_events {
+        *counts.entry((e.killer_puuid.as_str(), e.victim_puuid.as_str())).or_insert(0) += 1;
+    }
+
+    let mut edges: Vec<KillEdge> = counts
+        .into_iter()
+        .map(|((from, to), kill_count)| KillEdge {
+            from_puuid: from.to_string(),
+            to_puuid: to.to_string(),
+            kill_count,
+        })
+        .collect();
+    edges.sort_by(|a, b| a.from_puuid.cmp(&b.from_puuid).then_with(|| a.to_puuid.cmp(&b.to_puuid)));
+
+    KillGraph { nodes, edges }
+}
+
+/// Smallest rectangle in raw game coordinates holding every killer and victim position
+///
+/// The 0 / -999 placeholder positions are ignored. With no usable positions the
+/// bounds are all zero rather than inverted.
+pub fn compute_bounds(events: &[KillEvent]) -> Bounds {
+    let locations = events
+        .iter()
+        .flat_map(|e| e.killer_location.iter().chain([&e.victim_location]))
+        .filter(|l| l.x != 0 && l.y != 0 && l.x != -999 && l.y != -999);
+
+    let mut bounds: Option<Bounds> = None;
+    for l in locations {
+        let b = bounds.get_or_insert(Bounds { min_x: l.x, max_x: l.x, min_y: l.y, max_y: l.y });
+        b.min_x = b.min_x.min(l.x);
+        b.max_x = b.max_x.max(l.x);
+        b.min_y = b.min_y.min(l.y);
+        b.max_y = b.max_y.max(l.y);
+    }
+
+    bounds.unwrap_or(Bounds { min_x: 0, max_x: 0, min_y: 0, max_y: 0 })
+}
+
+/// Pick count, wins and average K/D per agent, most picked first
+///
+/// A pick counts as a win when the player's team is the match's `winning_team`.
+/// Observers and players without an agent are skipped.
+pub fn compute_agent_stats(details: &[MatchDetail]) -> Vec<AgentStats> {
+    // (picks, wins, total kills, total deaths) per agent
+    let mut totals: HashMap<String, (u32, u32, i64, i64)> = HashMap::new();
+
+    for detail in details {
+        for player in detail.players.iter().filter(|p| !p.is_observer) {
+            let Some(agent) = player.agent.as_deref() else {
+                continue;
+            };
+
+            let entry = totals.entry(json_processor::agent_display_name(agent)).or_insert((0, 0, 0, 0));
+            entry.0 += 1;
+            if player.team == detail.winning_team {
+                entry.1 += 1;
+            }
+            entry.2 += player.kills as i64;
+            entry.3 += player.deaths as i64;
+        }
+    }
+
+    let mut stats: Vec<AgentStats> = totals
+        .into_iter()
+        .map(|(agent_name, (picks, wins, kills, deaths))| AgentStats {
+            agent_name,
+            times_picked: picks,
+            wins,
+            avg_kills: kills as f64 / picks as f64,
+            avg_deaths: deaths as f64 / picks as f64,
+        })
+        .collect();
+
+    stats.sort_by(|a, b| b.times_picked.cmp(&a.times_picked).then_with(|| a.agent_name.cmp(&b.agent_name)));
+    stats
+}
+
+/// Average combat score per round; 0 when the player has no recorded rounds
+fn average_combat_score(player: &PlayerStats) -> f64 {
+    if player.rounds_played > 0 {
+        player.score as f64 / player.rounds_played as f64
+    } else {
+        0.0
+    }
+}
+
+/// Highest-kill player of each match as `(game_name#tag, puuid, kills)`
+///
+/// Kill ties go to the higher ACS. Observers are skipped, and matches without
+/// any players are left out.
+pub fn top_fraggers(folder_path: &str, match_ids: &[String]) -> Result<Vec<(String, String, i32)>, ProcessorError> {
+    let details = json_processor::get_multiple_match_details(Some(folder_path), match_ids)?;
+
+    Ok(details
+        .iter()
+        .filter_map(|detail| {
+            detail.players
+                .iter()
+                .filter(|p| !p.is_observer)
+                .max_by(|a, b| {
+                    a.kills
+                        .cmp(&b.kills)
+                        .then_with(|| average_combat_score(a).total_cmp(&average_combat_score(b)))
+                })
+        })
+        .map(|p| (format!("{}#{}", p.game_name, p.tag_line), p.puuid.clone(), p.kills))
+        .collect())
+}
+
+/// Picks and total kills and deaths per agent across the given matches, most picked first
+///
+/// Observers are skipped. Players without an agent, or with one missing from the
+/// agent table, are grouped under "Unknown".
+pub fn agent_stats(folder_path: &str, match_ids: &[String]) -> Result<Vec<AgentStat>, ProcessorError> {
+    let details = json_processor::get_multiple_match_details(Some(folder_path), match_ids)?;
+
+    let mut totals: HashMap<&str, AgentStat> = HashMap::new();
+    for player in details.iter().flat_map(|d| &d.players).filter(|p| !p.is_observer) {
+        let agent_name = player.agent.as_deref().and_then(json_processor::known_agent_name).unwrap_or("Unknown");
+
+        let stat = totals.entry(agent_name).or_insert_with(|| AgentStat {
+            agent_name: agent_name.to_string(),
+            picks: 0,
+            total_kills: 0,
+            total_deaths: 0,
+        });
+        stat.picks += 1;
+        stat.total_kills += player.kills;
+        stat.total_deaths += player.deaths;
+    }
+
+    let mut stats: Vec<AgentStat> = totals.into_values().collect();
+    stats.sort_by(|a, b| b.picks.cmp(&a.picks).then_with(|| a.agent_name.cmp(&b.agent_name)));
+    Ok(stats)
+}
+
+/// Order of simultaneous replay events, so a round closes before the next one opens
+fn replay_event_rank(event_type: ReplayEventType) -> u8 {
+    match event_type {
+        ReplayEventType::RoundEnd => 0,
+        ReplayEventType::RoundStart => 1,
+        ReplayEventType::Kill => 2,
+        ReplayEventType::PlantSpike => 3,
+        ReplayEventType::DefuseSpike => 4,
+    }
+}
+
+/// Merge round boundaries, kills, plants and defuses into one chronological stream
+///
+/// Round start times come from kills that carry a game time. Rounds without one
+/// are placed by assuming rounds of equal length, as the kill timeline does. A
+/// round ends when the next one starts; the last round ends at the game length
+/// or its last event, whichever is later.
+pub fn build_replay_stream(detail: &MatchDetail) -> Vec<ReplayEvent> {
+    let mut rounds: Vec<&RoundOutcome> = detail.rounds.iter().collect();
+    rounds.sort_by_key(|r| r.round_num);
+
+    let mut known_starts: HashMap<i32, i64> = HashMap::new();
+    for event in &detail.kill_events {
+        if let Some(game_time) = event.game_time_millis {
+            known_starts.entry(event.round_num).or_insert(game_time - event.round_time_millis as i64);
+        }
+    }
+    let average_round_millis = detail.game_length_millis.unwrap_or(0).max(0) / rounds.len().max(1) as i64;
+    let round_start = |round_num: i32| {
+        known_starts.get(&round_num).copied().unwrap_or(round_num as i64 * average_round_millis)
+    };
+
+    let mut stream = Vec::new();
+
+    for event in &detail.kill_events {
+        stream.push(ReplayEvent {
+            time_since_match_start_millis: event.game_time_millis
+                .unwrap_or(round_start(event.round_num) + event.round_time_millis as i64),
+            event_type: ReplayEventType::Kill,
+            payload: serde_json::to_value(event).unwrap_or(serde_json::Value::Null),
+        });
+    }
+
+    for round in &rounds {
+        let start = round_start(round.round_num);
+        let spikes = [
+            (ReplayEventType::PlantSpike, &round.plant),
+            (ReplayEventType::DefuseSpike, &round.defuse),
+        ];
+        for (event_type, spike) in spikes {
+            let Some(spike) = spike else {
+                continue;
+            };
+            stream.push(ReplayEvent {
+                time_since_match_start_millis: start + spike.round_time_millis as i64,
+                event_type,
+                payload: serde_json::json!({
+                    "round_num": round.round_num,
+                    "player_puuid": spike.player_puuid,
+                    "round_time_millis": spike.round_time_millis,
+                    "location": spike.location,
+                    "site": spike.site,
+                }),
+            });
+        }
+    }
+
+    for (i, round) in rounds.iter().enumerate() {
+        let start = round_start(round.round_num);
+        let end = match rounds.get(i + 1) {
+            Some(next) => round_start(next.round_num),
+            None => stream
+                .iter()
+                .map(|e| e.time_since_match_start_millis)
+                .chain(detail.game_length_millis)
+                .fold(start, i64::max),
+        };
+
+        stream.push(ReplayEvent {
+            time_since_match_start_millis: start,
+            event_type: ReplayEventType::RoundStart,
+            payload: serde_json::json!({ "round_num": round.round_num }),
+        });
+        stream.push(ReplayEvent {
+            time_since_match_start_millis: end,
+            event_type: ReplayEventType::RoundEnd,
+            payload: serde_json::json!({
+                "round_num": round.round_num,
+                "winning_team": round.winning_team,
+            }),
+        });
+    }
+
+    stream.sort_by_key(|e| (e.time_since_match_start_millis, replay_event_rank(e.event_type)));
+    stream
+}
+
+/// Round winners in round order; rounds without a winner come back as None
+fn winners_in_order(round_outcomes: &[RoundOutcome]) -> Vec<Option<&str>> {
+    let mut rounds: Vec<&RoundOutcome> = round_outcomes.iter().collect();
+    rounds.sort_by_key(|r| r.round_num);
+    rounds.iter().map(|r| r.winning_team.as_deref()).collect()
+}
+
+/// Longest run of consecutive round wins for each team that won a round
+///
+/// A round without a recorded winner ends every streak.
+pub fn win_streaks(round_outcomes: &[RoundOutcome]) -> HashMap<String, u32> {
+    let mut streaks: HashMap<String, u32> = HashMap::new();
+    let mut previous: Option<&str> = None;
+    let mut length = 0;
+
+    for winner in winners_in_order(round_outcomes) {
+        length = if winner.is_some() && winner == previous { length + 1 } else { 1 };
+        previous = winner;
+
+        if let Some(team) = winner {
+            let best = streaks.entry(team.to_string()).or_insert(0);
+            *best = (*best).max(length);
+        }
+    }
+
+    streaks
+}
+
+/// Team and length of the streak still running after the last round
+///
+/// Returns ("Unknown", 0) when there are no rounds or the last one has no winner.
+pub fn current_streak(round_outcomes: &[RoundOutcome]) -> (String, u32) {
+    let winners = winners_in_order(round_outcomes);
+    let Some(Some(team)) = winners.last().copied() else {
+        return ("Unknown".to_string(), 0);
+    };
+
+    let length = winners.iter().rev().take_while(|w| **w == Some(team)).count() as u32;
+    (team.to_string(), length)
+}
+
+/// Longest and closing win streaks for a match
+pub fn match_momentum(round_outcomes: &[RoundOutcome]) -> MatchMomentum {
+    let (current_streak_team, current_streak_length) = current_streak(round_outcomes);
+    MatchMomentum {
+        max_streaks: win_streaks(round_outcomes),
+        current_streak_team,
+        current_streak_length,
+    }
+}
+
+/// Mean minimap position of every kill a player was in, as killer or as victim
+///
+/// Positions that can't be placed on the map are skipped. Returns None when the
+/// player has no usable positions.
+pub fn player_position_centroid(events: &[KillEvent], puuid: &str, map: &str) -> Option<(f64, f64)> {
+    let positions: Vec<(f64, f64)> = events
+        .iter()
+        .flat_map(|e| {
+            let as_killer = e.killer_location.as_ref().filter(|_| e.killer_puuid == puuid);
+            let as_victim = Some(&e.victim_location).filter(|_| e.victim_puuid == puuid);
+            as_killer.into_iter().chain(as_victim)
+        })
+        .filter_map(|location| coordinates::to_uv(location, map))
+        .collect();
+
+    if positions.is_empty() {
+        return None;
+    }
+
+    let n = positions.len() as f64;
+    let (sum_u, sum_v) = positions.iter().fold((0.0, 0.0), |(su, sv), (u, v)| (su + u, sv + v));
+    Some((sum_u / n, sum_v / n))
+}
+
+/// Kill-position centroid for every non-observer player with at least one usable position
+pub fn all_player_centroids(events: &[KillEvent], players: &[PlayerStats], map: &str) -> Vec<PlayerCentroid> {
+    players
+        .iter()
+        .filter(|p| !p.is_observer)
+        .filter_map(|p| {
+            let (uv_x, uv_y) = player_position_centroid(events, &p.puuid, map)?;
+            Some(PlayerCentroid {
+                puuid: p.puuid.clone(),
+                game_name: p.game_name.clone(),
+                team: p.team.clone(),
+                uv_x,
+                uv_y,
+            })
+        })
+        .collect()
+}
+
+/// Kills between two players across every loaded match they both appear in
+///
+/// Shared matches are found through the player index, so only those match files are read.
+pub fn head_to_head(folder_path: &str, puuid_a: &str, puuid_b: &str) -> Result<HeadToHead, ProcessorError> {
+    let b_match_ids: Vec<String> = json_processor::matches_for_player(folder_path, puuid_b)?
+        .into_iter()
+        .map(|m| m.match_id)
+        .collect();
+    let shared: Vec<String> = json_processor::matches_for_player(folder_path, puuid_a)?
+        .into_iter()
+        .map(|m| m.match_id)
+        .filter(|match_id| b_match_ids.contains(match_id))
+        .collect();
+
+    let mut result = HeadToHead {
+        a_kills_b: 0,
+        b_kills_a: 0,
+        matches: shared.len() as u32,
+    };
+
+    for detail in json_processor::get_multiple_match_details(Some(folder_path), &shared)? {
+        for event in &detail.kill_events {
+            if event.killer_puuid == puuid_a && event.victim_puuid == puuid_b {
+                result.a_kills_b += 1;
+            } else if event.killer_puuid == puuid_b && event.victim_puuid == puuid_a {
+                result.b_kills_a += 1;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Rounds a player won alone while outnumbered, making the round's final kill themselves
+///
+/// These are the clutches from [`json_processor::detect_clutches`] that were won
+/// and closed out by the clutching player.
+pub fn won_clutches(detail: &MatchDetail) -> Vec<Clutch> {
+    json_processor::detect_clutches(detail)
+        .into_iter()
+        .filter(|c| c.won && c.got_final_kill)
+        .map(|c| Clutch {
+            puuid: c.clutch_player_puuid,
+            round_num: c.round_num,
+            enemies_remaining: c.opponents_remaining,
+        })
+        .collect()
+}
+
+/// Pair each kill with the minimap position of every player alive at that moment
+///
+/// Rounds don't say which team each player is on, so `round_won_by_killer` is
+/// left unknown. Positions that can't be placed on the map are left out of a snapshot.
+pub fn extract_kill_snapshots(rounds: &[RoundResult], map: &str) -> Vec<KillSnapshot> {
+    let (kills, _) = json_processor::extract_kills(rounds, &HashMap::new());
+
+    kills
+        .into_iter()
+        .map(|(kill, event)| KillSnapshot {
+            all_positions: kill.player_locations
+                .iter()
+                .filter_map(|pl| {
+                    let (uv_x, uv_y) = coordinates::to_uv(&pl.location, map)?;
+                    Some(PlayerPosition {
+                        puuid: pl.puuid.clone(),
+                        uv_x,
+                        uv_y,
+                    })
+                })
+                .collect(),
+            event,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[test]
+    fn single_cluster_centroid_is_the_mean_position() {
+        let positions = [(1000, -3000), (2000, -4000), (3000, -8000)];
+        let kills = positions
+            .iter()
+            .enumerate()
+            .map(|(i, &at)| kill("b0", &format!("r{}", i), 1_000, at))
+            .collect();
+        let events = kill_events(vec![round(0, "Blue", kills)]);
+
+        let clusters = cluster_kill_locations("Ascent", &events, 1, KMEANS_MAX_ITER);
+
+        let uvs: Vec<(f64, f64)> = events
+            .iter()
+            .map(|e| coordinates::to_uv(&e.victim_location, "Ascent").unwrap())
+            .collect();
+        let mean_u = uvs.iter().map(|uv| uv.0).sum::<f64>() / 3.0;
+        let mean_v = uvs.iter().map(|uv| uv.1).sum::<f64>() / 3.0;
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].member_count, 3);
+        assert!((clusters[0].centroid_uv.0 - mean_u).abs() < 1e-9);
+        assert!((clusters[0].centroid_uv.1 - mean_v).abs() < 1e-9);
+        assert_eq!(clusters[0].representative_weapon.as_deref(), Some("Vandal"));
+    }
+
+    #[test]
+    fn dbscan_separates_two_clusters_from_noise() {
+        let positions = [
+            (1000, -3000), (1020, -3000), (1000, -3020), (1020, -3020),
+            (-4000, -8000), (-4020, -8000), (-4000, -8020), (-4020, -8020),
+            (5000, 1000), (-7000, -1000),
+        ];
+        let kills = positions.iter().map(|&at| kill("b0", "r0", 1_000, at)).collect();
+        let events = kill_events(vec![round(0, "Blue", kills)]);
+
+        let mut clusters = dbscan_kill_locations("Ascent", &events, 0.01, 3);
+        for cluster in &mut clusters {
+            cluster.core_members.sort();
+        }
+        clusters.sort_by_key(|c| c.core_members[0]);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].core_members, vec![0, 1, 2, 3]);
+        assert_eq!(clusters[1].core_members, vec![4, 5, 6, 7]);
+        let mut outliers: Vec<usize> = clusters.iter().flat_map(|c| c.outliers.iter().copied()).collect();
+        outliers.sort();
+        assert_eq!(outliers, vec![8, 9]);
+    }
+
+    /// Mean bucket index of a histogram, weighted by count
+    fn mean_bucket(histogram: &DistributionHistogram) -> f64 {
+        let total: u32 = histogram.counts.iter().sum();
+        let weighted: f64 = histogram.counts.iter().enumerate().map(|(i, &c)| i as f64 * c as f64).sum();
+        weighted / total as f64
+    }
+
+    #[test]
+    fn operator_kills_are_longer_range_than_classic_kills() {
+        const CLASSIC: &str = "29A0CFAB-485B-F5D5-779A-B59F85E204A8";
+        let kills = (0..4)
+            .flat_map(|i| {
+                let victim_at = (1000 + i * 100, -3000);
+                [
+                    with_killer_at(with_weapon(kill("b0", "r0", 1_000, victim_at), OPERATOR), (victim_at.0 - 4000, -6000)),
+                    with_killer_at(with_weapon(kill("b1", "r1", 1_000, victim_at), CLASSIC), (victim_at.0 - 300, -3000)),
+                ]
+            })
+            .collect();
+        let events = kill_events(vec![round(0, "Blue", kills)]);
+
+        let operator = kill_distance_distribution(&events, "Ascent", "operator", 10);
+        let classic = kill_distance_distribution(&events, "Ascent", "Classic", 10);
+
+        assert_eq!(operator.counts.iter().sum::<u32>(), 4);
+        assert_eq!(classic.counts.iter().sum::<u32>(), 4);
+        assert!(mean_bucket(&operator) > mean_bucket(&classic));
+    }
+
+    #[test]
+    fn won_one_v_two_is_a_clutch() {
+        let detail = parse_detail(&vct_match("clutch", ten_players(), vec![clutch_round(0, 2, true)]));
+
+        let clutches = won_clutches(&detail);
+
+        assert_eq!(clutches.len(), 1);
+        assert_eq!(clutches[0].puuid, "b4");
+        assert_eq!(clutches[0].round_num, 0);
+        assert_eq!(clutches[0].enemies_remaining, 2);
+    }
+
+    #[test]
+    fn lost_one_v_n_is_not_a_clutch() {
+        let detail = parse_detail(&vct_match("clutch", ten_players(), vec![clutch_round(0, 3, false)]));
+
+        assert!(won_clutches(&detail).is_empty());
+    }
+
+    #[test]
+    fn bounds_cover_killer_and_victim_positions() {
+        let events = kill_events(vec![round(0, "Blue", vec![
+            with_killer_at(kill("b0", "r0", 1_000, (1000, -3000)), (-2000, -500)),
+            kill("b1", "r1", 2_000, (4000, -6000)),
+        ])]);
+
+        let bounds = compute_bounds(&events);
+
+        assert_eq!(bounds, Bounds { min_x: -2000, max_x: 4000, min_y: -6000, max_y: -500 });
+    }
+
+    #[test]
+    fn bounds_of_no_events_are_zero_sized() {
+        assert_eq!(compute_bounds(&[]), Bounds { min_x: 0, max_x: 0, min_y: 0, max_y: 0 });
+    }
+
+    /// [`ten_players`] with every Blue player on `blue_agent` and every Red player on `red_agent`
+    fn players_on_agents(blue_agent: &str, red_agent: &str) -> Vec<serde_json::Value> {
+        ten_players()
+            .into_iter()
+            .map(|mut player| {
+                let agent = if player["teamId"] == "Blue" { blue_agent } else { red_agent };
+                player["characterId"] = serde_json::json!(agent);
+                player
+            })
+            .collect()
+    }
+
+    #[test]
+    fn agent_wins_follow_the_winning_team() {
+        const JETT: &str = "ADD6443A-41BD-E414-F6AD-E58D267F4E95";
+        const REYNA: &str = "A3BFB853-43B2-7238-A4F1-AD90E9E46BCC";
+        let blue_win = parse_detail(&vct_match("m1", players_on_agents(JETT, REYNA), vec![round(0, "Blue", Vec::new())]));
+        let red_win = parse_detail(&vct_match("m2", players_on_agents(REYNA, JETT), vec![round(0, "Red", Vec::new())]));
+
+        let stats = compute_agent_stats(&[blue_win, red_win]);
+        let summary: Vec<(&str, u32, u32)> = stats
+            .iter()
+            .map(|s| (s.agent_name.as_str(), s.times_picked, s.wins))
+            .collect();
+
+        assert_eq!(summary, vec![("Jett", 10, 10), ("Reyna", 10, 0)]);
+    }
+
+    #[test]
+    fn known_killer_position_gives_a_reliable_distance() {
+        let events = kill_events(vec![round(0, "Blue", vec![
+            with_killer_at(kill("b0", "r0", 1_000, (1300, -3400)), (1000, -3000)),
+        ])]);
+
+        assert_eq!(events[0].distance, 500.0);
+        assert!(events[0].distance_reliable);
+    }
+
+    #[test]
+    fn missing_killer_position_is_unreliable_and_left_out_of_the_histogram() {
+        let events = kill_events(vec![round(0, "Blue", vec![
+            with_killer_at(kill("b0", "r0", 1_000, (1300, -3400)), (1000, -3000)),
+            kill("b1", "r1", 2_000, (1000, -3000)),
+        ])]);
+
+        let fallback = events.iter().find(|e| e.killer_puuid == "b1").unwrap();
+        assert_eq!(fallback.distance, 0.0);
+        assert!(!fallback.distance_reliable);
+
+        assert_eq!(distance_histogram(&events, 200.0), vec![(0.0, 0), (200.0, 0), (400.0, 1)]);
+    }
+
+    fn player_with_stats(puuid: &str, team: &str, kills: i32, score: i32) -> serde_json::Value {
+        let mut player = player(puuid, team);
+        player["stats"]["kills"] = serde_json::json!(kills);
+        player["stats"]["score"] = serde_json::json!(score);
+        player["stats"]["roundsPlayed"] = serde_json::json!(20);
+        player
+    }
+
+    #[test]
+    fn top_fragger_is_picked_per_match_with_acs_breaking_ties() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        write_match(dir.path(), "m1.json", &vct_match("m1", vec![
+            player_with_stats("b0", "Blue", 25, 5_000),
+            player_with_stats("r0", "Red", 18, 6_000),
+        ], Vec::new()));
+        write_match(dir.path(), "m2.json", &vct_match("m2", vec![
+            player_with_stats("b0", "Blue", 15, 4_000),
+            player_with_stats("r0", "Red", 15, 4_500),
+        ], Vec::new()));
+        let folder = path_str(dir.path());
+        json_processor::load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        let fraggers = top_fraggers(folder, &["m1".to_string(), "m2".to_string()]).unwrap();
+
+        assert_eq!(fraggers, vec![
+            ("B0#TEST".to_string(), "b0".to_string(), 25),
+            ("R0#TEST".to_string(), "r0".to_string(), 15),
+        ]);
+    }
+
+    fn outcomes(winners: &[&str]) -> Vec<RoundOutcome> {
+        let rounds = winners.iter().enumerate().map(|(i, winner)| round(i as i32, winner, vec![])).collect();
+        parse_detail(&vct_match("test", ten_players(), rounds)).rounds
+    }
+
+    #[test]
+    fn five_straight_blue_wins_are_one_streak() {
+        let rounds = outcomes(&["Red", "Blue", "Blue", "Blue", "Blue", "Blue"]);
+
+        let streaks = win_streaks(&rounds);
+        assert_eq!(streaks["Blue"], 5);
+        assert_eq!(streaks["Red"], 1);
+        assert_eq!(current_streak(&rounds), ("Blue".to_string(), 5));
+    }
+
+    #[test]
+    fn red_streak_of_five_ends_the_match() {
+        let rounds = outcomes(&["Blue", "Blue", "Red", "Red", "Red", "Red", "Red"]);
+
+        let momentum = match_momentum(&rounds);
+        assert_eq!(momentum.max_streaks["Red"], 5);
+        assert_eq!(momentum.max_streaks["Blue"], 2);
+        assert_eq!(momentum.current_streak_team, "Red");
+        assert_eq!(momentum.current_streak_length, 5);
+    }
+
+    #[test]
+    fn alternating_winners_never_streak_past_one() {
+        let rounds = outcomes(&["Blue", "Red", "Blue", "Red", "Blue", "Red"]);
+
+        let streaks = win_streaks(&rounds);
+        assert_eq!(streaks["Blue"], 1);
+        assert_eq!(streaks["Red"], 1);
+        assert_eq!(current_streak(&rounds), ("Red".to_string(), 1));
+        assert_eq!(current_streak(&[]), ("Unknown".to_string(), 0));
+    }
+
+    #[test]
+    fn head_to_head_counts_traded_kills_in_shared_matches() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let first = vec![
+            round(0, "Blue", vec![kill("b0", "r0", 10_000, (1000, -3000))]),
+            round(1, "Red", vec![kill("r0", "b0", 10_000, (1000, -3000))]),
+        ];
+        let second = vec![round(0, "Blue", vec![kill("b0", "r0", 10_000, (1000, -3000)), kill("b0", "r1", 12_000, (1000, -3000))])];
+        let without_r0: Vec<serde_json::Value> = ten_players().into_iter().filter(|p| p["puuid"] != "r0").collect();
+        let third = vec![round(0, "Blue", vec![kill("b0", "r1", 10_000, (1000, -3000))])];
+        write_match(dir.path(), "m1.json", &vct_match("m1", ten_players(), first));
+        write_match(dir.path(), "m2.json", &vct_match("m2", ten_players(), second));
+        write_match(dir.path(), "m3.json", &vct_match("m3", without_r0, third));
+        let folder = path_str(dir.path());
+        json_processor::load_json_files(folder, None, &crate::models::LoadOptions::default()).unwrap();
+
+        let result = head_to_head(folder, "b0", "r0").unwrap();
+        assert_eq!(result.a_kills_b, 2);
+        assert_eq!(result.b_kills_a, 1);
+        assert_eq!(result.matches, 2);
+    }
+
+    #[test]
+    fn agent_totals_add_up_across_matches() {
+        const JETT: &str = "ADD6443A-41BD-E414-F6AD-E58D267F4E95";
+        const REYNA: &str = "A3BFB853-43B2-7238-A4F1-AD90E9E46BCC";
+        let with_kd = |players: Vec<serde_json::Value>, kills: i32, deaths: i32| -> Vec<serde_json::Value> {
+            players
+                .into_iter()
+                .map(|mut player| {
+                    player["stats"]["kills"] = serde_json::json!(kills);
+                    player["stats"]["deaths"] = serde_json::json!(deaths);
+                    player
+                })
+                .collect()
+        };
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let first = vct_match("m1", with_kd(players_on_agents(JETT, REYNA), 2, 1), vec![round(0, "Blue", Vec::new())]);
+        let second = vct_match("m2", with_kd(players_on_agents(JETT, "not-an-agent"), 3, 2), vec![round(0, "Red", Vec::new())]);
+        write_match(dir.path(), "m1.json", &first);
+        write_match(dir.path(), "m2.json", &second);
+        let folder = path_str(dir.path());
+        json_processor::load_json_files(folder, None, &crate::models::LoadOptions::default()).unwrap();
+
+        let stats = agent_stats(folder, &["m1".to_string(), "m2".to_string()]).unwrap();
+        let totals: Vec<(&str, u32, i32, i32)> = stats
+            .iter()
+            .map(|s| (s.agent_name.as_str(), s.picks, s.total_kills, s.total_deaths))
+            .collect();
+
+        assert_eq!(totals, vec![("Jett", 10, 25, 15), ("Reyna", 5, 10, 5), ("Unknown", 5, 15, 10)]);
+    }
+}
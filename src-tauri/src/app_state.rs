@@ -0,0 +1,105 @@
+use crate::error::ProcessorError;
+use crate::models::{AppState, UserPrefs};
+use crate::tags::TagStore;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const STATE_FILE_NAME: &str = "app_state.json";
+const PREFS_FILE_NAME: &str = "user_prefs.json";
+
+fn state_file(config_dir: &Path) -> PathBuf {
+    config_dir.join(STATE_FILE_NAME)
+}
+
+/// Write the app state to the config directory
+pub fn save_app_state(state: &AppState, config_dir: &Path) -> Result<(), ProcessorError> {
+    fs::create_dir_all(config_dir)?;
+    let contents = serde_json::to_string_pretty(state)?;
+    Ok(fs::write(state_file(config_dir), contents)?)
+}
+
+/// Read the app state from the config directory, falling back to defaults if missing or corrupt
+pub fn load_app_state(config_dir: &Path) -> AppState {
+    let path = state_file(config_dir);
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return AppState::default(),
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        eprintln!("Ignoring corrupt app state {}: {}", path.display(), e);
+        AppState::default()
+    })
+}
+
+/// Read user preferences from the app data directory, falling back to defaults if missing or corrupt
+pub fn load_prefs(app: &AppHandle) -> UserPrefs {
+    let path = match app.path().app_data_dir() {
+        Ok(data_dir) => data_dir.join(PREFS_FILE_NAME),
+        Err(_) => return UserPrefs::default(),
+    };
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return UserPrefs::default(),
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        eprintln!("Ignoring corrupt preferences {}: {}", path.display(), e);
+        UserPrefs::default()
+    })
+}
+
+/// Write user preferences to the app data directory
+pub fn save_prefs(app: &AppHandle, prefs: &UserPrefs) -> Result<(), ProcessorError> {
+    let data_dir = app_data_dir(app)?;
+    fs::create_dir_all(&data_dir)?;
+    let contents = serde_json::to_string_pretty(prefs)?;
+    Ok(fs::write(data_dir.join(PREFS_FILE_NAME), contents)?)
+}
+
+/// Open the match tag store in the app data directory
+pub fn tag_store(app: &AppHandle) -> Result<TagStore, ProcessorError> {
+    Ok(TagStore::open(&app_data_dir(app)?))
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, ProcessorError> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| ProcessorError::Other(format!("Failed to resolve data directory: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn app_state_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join("config");
+        let state = AppState {
+            last_folder: Some("/data/vct".to_string()),
+            last_filters: Some(json!({ "maps": ["Ascent"] })),
+        };
+
+        save_app_state(&state, &config_dir).unwrap();
+        let loaded = load_app_state(&config_dir);
+
+        assert_eq!(loaded.last_folder, state.last_folder);
+        assert_eq!(loaded.last_filters, state.last_filters);
+    }
+
+    #[test]
+    fn missing_or_corrupt_state_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_app_state(dir.path()).last_folder.is_none());
+
+        fs::write(state_file(dir.path()), "{ not json").unwrap();
+        let loaded = load_app_state(dir.path());
+        assert!(loaded.last_folder.is_none());
+        assert!(loaded.last_filters.is_none());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::models::{Location, MapCalibration};
+
+/// Calibration for a single map: the game-to-minimap transform and the minimap it targets
+///
+/// Mirrors `MAP_TRANSFORMS` in the frontend's coordinateTransform.ts, which
+/// takes its values from https://valorant-api.com/v1/maps.
+#[derive(Debug, Clone, Copy)]
+pub struct MapTransform {
+    pub x_multiplier: f64,
+    pub y_multiplier: f64,
+    pub x_scalar_to_add: f64,
+    pub y_scalar_to_add: f64,
+    /// valorant-api map UUID, which names the minimap image
+    pub minimap_asset_key: &'static str,
+    /// Pixel size of the minimap (`displayicon.png`)
+    pub image_width: u32,
+    pub image_height: u32,
+}
+
+const fn transform(
+    multiplier: f64,
+    x_scalar_to_add: f64,
+    y_scalar_to_add: f64,
+    minimap_asset_key: &'static str,
+    image_size: (u32, u32)
+) -> MapTransform {
+    MapTransform {
+        x_multiplier: multiplier,
+        y_multiplier: -multiplier,
+        x_scalar_to_add,
+        y_scalar_to_add,
+        minimap_asset_key,
+        image_width: image_size.0,
+        image_height: image_size.1,
+    }
+}
+
+/// Transforms keyed by map display name
+const MAP_TRANSFORMS: &[(&str, MapTransform)] = &[
+    ("Abyss", transform(0.000081, 0.5, 0.5, "224b0a95-48b9-f703-1bd8-67aca101a61f", (1024, 1024))),
+    ("Ascent", transform(0.00007, 0.813895, 0.573242, "7eaecc1b-4337-bbf6-6ab9-04b8f06b3319", (1024, 1024))),
+    ("Bind", transform(0.000059, 0.576941, 0.967566, "2c9d57ec-4431-9c5e-2939-8f9ef6dd5cba", (1024, 1024))),
+    ("Breeze", transform(0.00007, 0.465123, 0.833078, "2fb9a4fd-47b8-4e7d-a969-74b4046ebd53", (1024, 1024))),
+    ("Corrode", transform(0.00007, 0.526158, 0.5, "1c18ab1f-420d-0d8b-71d0-77ad3c439115", (1024, 1024))),
+    ("Fracture", transform(0.000078, 0.556952, 1.155886, "b529448b-4d60-346e-e89e-00a4c527a405", (1024, 1024))),
+    ("Haven", transform(0.000075, 1.09345, 0.642728, "2bee0dc9-4ffe-519b-1cbd-7fbe763a6047", (1024, 1024))),
+    ("Icebox", transform(0.000072, 0.460214, 0.304687, "e2ad5c54-4114-a870-9641-8ea21279579a", (1024, 1024))),
+    ("Lotus", transform(0.000072, 0.454789, 0.917752, "2fe4ed3a-450a-948b-6d6b-e89a78e680a9", (1024, 1024))),
+    ("Pearl", transform(0.000078, 0.480469, 0.916016, "fd267378-4d1d-484f-ff52-77821ed10dc2", (1024, 1024))),
+    ("Split", transform(0.000078, 0.842188, 0.697578, "d960549e-485c-e861-8d71-aa9d1aed12a2", (1024, 1024))),
+    ("Sunset", transform(0.000078, 0.5, 0.515625, "92584fbe-486a-b1b2-9faa-39b0f486b498", (1024, 1024))),
+    ("Triad", transform(0.000063, 0.5, 0.5, "9c91a445-4f78-1baa-a3ea-8f8aadf4914d", (1024, 1024))),
+];
+
+/// Look up the transform for a map display name
+pub fn map_transform(map_display: &str) -> Option<MapTransform> {
+    MAP_TRANSFORMS
+        .iter()
+        .find(|(name, _)| *name == map_display)
+        .map(|(_, t)| *t)
+}
+
+/// Calibration for a map display name, as sent to the frontend
+///
+/// UV coordinates from [`to_uv`] scale by the image dimensions to land on the minimap.
+pub fn map_calibration(map_display: &str) -> Option<MapCalibration> {
+    map_transform(map_display).map(|t| MapCalibration {
+        map: map_display.to_string(),
+        x_multiplier: t.x_multiplier,
+        y_multiplier: t.y_multiplier,
+        x_scalar_to_add: t.x_scalar_to_add,
+        y_scalar_to_add: t.y_scalar_to_add,
+        image_width: t.image_width,
+        image_height: t.image_height,
+        minimap_asset_key: t.minimap_asset_key.to_string(),
+    })
+}
+
+/// Transform game coordinates to normalized [0, 1] minimap space
+///
+/// Game X and Y are swapped per the Valorant API. Returns None for unknown
+/// maps and for the 0 / -999 placeholders found in VCT exports.
+pub fn to_uv(location: &Location, map_display: &str) -> Option<(f64, f64)> {
+    if location.x == -999 || location.y == -999 || location.x == 0 || location.y == 0 {
+        return None;
+    }
+
+    let t = map_transform(map_display)?;
+    let u = location.y as f64 * t.x_multiplier + t.x_scalar_to_add;
+    let v = location.x as f64 * t.y_multiplier + t.y_scalar_to_add;
+
+    Some((u.clamp(0.0, 1.0), v.clamp(0.0, 1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_map_has_minimap_dimensions() {
+        let calibration = map_calibration("Ascent").unwrap();
+
+        assert!(calibration.image_width > 0);
+        assert!(calibration.image_height > 0);
+        assert_eq!(calibration.minimap_asset_key, "7eaecc1b-4337-bbf6-6ab9-04b8f06b3319");
+        assert_eq!(calibration.x_multiplier, 0.00007);
+    }
+
+    #[test]
+    fn unknown_map_has_no_calibration() {
+        assert!(map_calibration("Not A Map").is_none());
+    }
+}
@@ -0,0 +1,100 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+/// Errors returned by the match processor and the Tauri commands
+///
+/// Serializes as `{ kind, message }` so the frontend can switch on `kind`
+/// while still showing a readable message.
+#[derive(Debug)]
+pub enum ProcessorError {
+    FolderNotFound(String),
+    FileRead(std::io::Error),
+    Parse(serde_json::Error),
+    MatchNotFound(String),
+    /// Well-formed JSON that isn't usable match data
+    InvalidData(String),
+    Cancelled,
+    /// Any other failure, described by its message
+    Other(String),
+}
+
+impl ProcessorError {
+    /// Variant name sent to the frontend
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ProcessorError::FolderNotFound(_) => "FolderNotFound",
+            ProcessorError::FileRead(_) => "FileRead",
+            ProcessorError::Parse(_) => "Parse",
+            ProcessorError::MatchNotFound(_) => "MatchNotFound",
+            ProcessorError::InvalidData(_) => "InvalidData",
+            ProcessorError::Cancelled => "Cancelled",
+            ProcessorError::Other(_) => "Other",
+        }
+    }
+}
+
+impl fmt::Display for ProcessorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessorError::FolderNotFound(folder) => write!(f, "Folder does not exist: {}", folder),
+            ProcessorError::FileRead(e) => write!(f, "Failed to read file: {}", e),
+            ProcessorError::Parse(e) => write!(f, "Invalid JSON: {}", e),
+            ProcessorError::MatchNotFound(match_id) => write!(f, "Match not found with ID: {}", match_id),
+            ProcessorError::InvalidData(reason) => write!(f, "Invalid match data: {}", reason),
+            ProcessorError::Cancelled => write!(f, "Operation was cancelled"),
+            ProcessorError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ProcessorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProcessorError::FileRead(e) => Some(e),
+            ProcessorError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for ProcessorError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ProcessorError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<std::io::Error> for ProcessorError {
+    fn from(e: std::io::Error) -> Self {
+        ProcessorError::FileRead(e)
+    }
+}
+
+impl From<serde_json::Error> for ProcessorError {
+    fn from(e: serde_json::Error) -> Self {
+        ProcessorError::Parse(e)
+    }
+}
+
+impl From<ProcessorError> for String {
+    fn from(e: ProcessorError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_and_message() {
+        let error = ProcessorError::MatchNotFound("m1".to_string());
+
+        let value = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(value["kind"], "MatchNotFound");
+        assert_eq!(value["message"], "Match not found with ID: m1");
+    }
+}
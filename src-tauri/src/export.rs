@@ -0,0 +1,454 @@
+use crate::coordinates;
+use crate::error::ProcessorError;
+use crate::heatmap;
+use crate::json_processor;
+use crate::models::*;
+use arrow2::array::{Array, Float64Array, Int32Array, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{Field, Schema};
+use arrow2::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+const VIRIDIS: &[[u8; 3]] = &[
+    [68, 1, 84],
+    [59, 82, 139],
+    [33, 145, 140],
+    [94, 201, 98],
+    [253, 231, 37],
+];
+
+const INFERNO: &[[u8; 3]] = &[
+    [0, 0, 4],
+    [66, 10, 104],
+    [147, 38, 103],
+    [221, 81, 58],
+    [252, 165, 10],
+    [252, 255, 164],
+];
+
+/// Linearly interpolate between evenly spaced color stops
+fn interpolate(stops: &[[u8; 3]], t: f64) -> [u8; 3] {
+    let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let i = (scaled.floor() as usize).min(stops.len() - 2);
+    let frac = scaled - i as f64;
+
+    let mut color = [0; 3];
+    for (channel, value) in color.iter_mut().enumerate() {
+        let a = stops[i][channel] as f64;
+        let b = stops[i + 1][channel] as f64;
+        *value = (a + (b - a) * frac).round() as u8;
+    }
+    color
+}
+
+/// RGB color for an intensity between 0 and 1
+pub fn colormap_rgb(colormap: Colormap, t: f64) -> [u8; 3] {
+    match colormap {
+        Colormap::Grayscale => {
+            let v = (t.clamp(0.0, 1.0) * 255.0).round() as u8;
+            [v, v, v]
+        }
+        Colormap::Viridis => interpolate(VIRIDIS, t),
+        Colormap::Inferno => interpolate(INFERNO, t),
+    }
+}
+
+/// Log-scaled intensity of each cell, normalized so the busiest cell is 1
+pub fn log_normalize(counts: &[u32]) -> Vec<f64> {
+    log_normalize_weights(&counts.iter().map(|&count| count as f64).collect::<Vec<_>>())
+}
+
+/// [`log_normalize`] for fractional cell weights
+pub fn log_normalize_weights(weights: &[f64]) -> Vec<f64> {
+    let max = weights.iter().copied().fold(0.0, f64::max);
+    if max <= 0.0 {
+        return vec![0.0; weights.len()];
+    }
+
+    let denominator = (1.0 + max).ln();
+    weights
+        .iter()
+        .map(|&weight| (1.0 + weight.max(0.0)).ln() / denominator)
+        .collect()
+}
+
+/// Write a row-major count grid as a PNG with one pixel per cell
+///
+/// Empty cells are fully transparent so the image can be laid over a minimap.
+pub fn render_counts_png(
+    counts: &[u32],
+    grid_width: u32,
+    grid_height: u32,
+    colormap: Colormap,
+    out_path: &Path
+) -> Result<(), ProcessorError> {
+    let weights: Vec<f64> = counts.iter().map(|&count| count as f64).collect();
+    render_weights_png(&weights, grid_width, grid_height, colormap, out_path)
+}
+
+/// [`render_counts_png`] for fractional cell weights
+pub fn render_weights_png(
+    weights: &[f64],
+    grid_width: u32,
+    grid_height: u32,
+    colormap: Colormap,
+    out_path: &Path
+) -> Result<(), ProcessorError> {
+    if weights.len() != (grid_width * grid_height) as usize {
+        return Err(ProcessorError::Other(format!(
+            "Grid has {} cells but {}x{} was requested",
+            weights.len(),
+            grid_width,
+            grid_height
+        )));
+    }
+
+    let mut image = image::RgbaImage::new(grid_width, grid_height);
+    for (i, intensity) in log_normalize_weights(weights).into_iter().enumerate() {
+        let [r, g, b] = colormap_rgb(colormap, intensity);
+        let alpha = if weights[i] <= 0.0 { 0 } else { 255 };
+        image.put_pixel(i as u32 % grid_width, i as u32 / grid_width, image::Rgba([r, g, b, alpha]));
+    }
+
+    image
+        .save_with_format(out_path, image::ImageFormat::Png)
+        .map_err(|e| match e {
+            image::ImageError::IoError(e) => ProcessorError::FileRead(e),
+            e => ProcessorError::Other(format!("Failed to write {}: {}", out_path.display(), e)),
+        })
+}
+
+/// Write sparse grid cells as a square heatmap PNG using the default colormap
+///
+/// Cells outside the grid are rejected rather than silently dropped.
+pub fn render_heatmap_png(cells: &[GridCell], grid_size: u32, out_path: &str) -> Result<(), ProcessorError> {
+    let mut weights = vec![0.0; (grid_size * grid_size) as usize];
+    for cell in cells {
+        if cell.x >= grid_size || cell.y >= grid_size {
+            return Err(ProcessorError::Other(format!(
+                "Cell ({}, {}) is outside a {}x{} grid",
+                cell.x, cell.y, grid_size, grid_size
+            )));
+        }
+        weights[(cell.y * grid_size + cell.x) as usize] += cell.weight;
+    }
+
+    render_weights_png(&weights, grid_size, grid_size, Colormap::default(), Path::new(out_path))
+}
+
+/// Escape text for use in XML attribute values
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Pixel width of exported SVG heatmaps; the height follows the grid's aspect ratio
+const SVG_WIDTH: u32 = 1024;
+
+/// Write killer positions as an SVG grid of colored cells, optionally over a map image
+///
+/// The SVG uses one user unit per cell. Each non-empty cell is a `<rect>` colored
+/// by its log-scaled count, with `fill-opacity` proportional to its count relative
+/// to the busiest cell. The background image is referenced by path, not embedded.
+pub fn render_heatmap_to_svg(
+    heatmap: &KillHeatmap,
+    background_image_path: Option<&Path>,
+    colormap: Colormap,
+    output_path: &Path
+) -> Result<(), ProcessorError> {
+    let (width, height) = (heatmap.grid_width, heatmap.grid_height);
+    let counts = &heatmap.killer_counts;
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        SVG_WIDTH,
+        SVG_WIDTH as u64 * height as u64 / width.max(1) as u64,
+        width,
+        height
+    );
+
+    if let Some(path) = background_image_path {
+        svg.push_str(&format!(
+            "  <image href=\"{}\" x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" preserveAspectRatio=\"none\"/>\n",
+            xml_escape(&path.to_string_lossy()),
+            width,
+            height
+        ));
+    }
+
+    for (i, intensity) in log_normalize(counts).into_iter().enumerate() {
+        if counts[i] == 0 {
+            continue;
+        }
+        let [r, g, b] = colormap_rgb(colormap, intensity);
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\" fill-opacity=\"{:.3}\"/>\n",
+            i as u32 % width,
+            i as u32 / width,
+            r,
+            g,
+            b,
+            counts[i] as f64 / max
+        ));
+    }
+    svg.push_str("</svg>\n");
+
+    Ok(fs::write(output_path, svg)?)
+}
+
+/// Quote a string as a DOT identifier
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Write a kill graph in Graphviz DOT format, coloring players by team
+pub fn export_kill_graph_dot(graph: &KillGraph, writer: &mut impl Write) -> Result<(), ProcessorError> {
+    writeln!(writer, "digraph kills {{")?;
+    for node in &graph.nodes {
+        let color = match node.team.as_str() {
+            "Blue" => "blue",
+            "Red" => "red",
+            _ => "gray",
+        };
+        writeln!(writer, "  {} [label={}, color={}];", dot_quote(&node.puuid), dot_quote(&node.game_name), color)?;
+    }
+    for edge in &graph.edges {
+        writeln!(
+            writer,
+            "  {} -> {} [label={}, weight={}];",
+            dot_quote(&edge.from_puuid),
+            dot_quote(&edge.to_puuid),
+            edge.kill_count,
+            edge.kill_count
+        )?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+/// Write a match as pretty-printed JSON in the app's normalized schema
+///
+/// Minimap positions are computed up front, so the file can be reloaded without
+/// the raw VCT parsing or the map transforms.
+pub fn export_normalized_json(detail: &MatchDetail, writer: &mut impl Write) -> Result<(), ProcessorError> {
+    let kill_uvs = detail.kill_events
+        .iter()
+        .map(|event| {
+            let killer = event.killer_location.as_ref().and_then(|l| coordinates::to_uv(l, &detail.map_display));
+            let victim = coordinates::to_uv(&event.victim_location, &detail.map_display);
+            KillUv {
+                killer_u: killer.map(|uv| uv.0),
+                killer_v: killer.map(|uv| uv.1),
+                victim_u: victim.map(|uv| uv.0),
+                victim_v: victim.map(|uv| uv.1),
+            }
+        })
+        .collect();
+
+    let normalized = NormalizedMatch {
+        detail: detail.clone(),
+        kill_uvs,
+    };
+    Ok(serde_json::to_writer_pretty(writer, &normalized)?)
+}
+
+/// Write a match's kill events as a single-row-group Parquet file
+///
+/// One row per kill. Raw positions are nullable ints and minimap positions are
+/// nullable floats, null wherever the position is unknown or off the map.
+pub fn export_kill_events_to_parquet(events: &[KillEvent], map: &str, output_path: &Path) -> Result<(), ProcessorError> {
+    let parquet_err = |e: arrow2::error::Error| match e {
+        arrow2::error::Error::Io(e) => ProcessorError::FileRead(e),
+        e => ProcessorError::Other(format!("Failed to write {}: {}", output_path.display(), e)),
+    };
+
+    let killer_uv: Vec<Option<(f64, f64)>> = events
+        .iter()
+        .map(|e| e.killer_location.as_ref().and_then(|l| coordinates::to_uv(l, map)))
+        .collect();
+    let victim_uv: Vec<Option<(f64, f64)>> = events
+        .iter()
+        .map(|e| coordinates::to_uv(&e.victim_location, map))
+        .collect();
+
+    let columns: Vec<(&str, Box<dyn Array>)> = vec![
+        ("map", Utf8Array::<i32>::from_iter_values(events.iter().map(|_| map)).boxed()),
+        ("round_num", Int32Array::from_iter(events.iter().map(|e| Some(e.round_num))).boxed()),
+        ("round_phase", Utf8Array::<i32>::from_iter_values(events.iter().map(|e| format!("{:?}", e.round_phase))).boxed()),
+        ("round_time_millis", Int32Array::from_iter(events.iter().map(|e| Some(e.round_time_millis))).boxed()),
+        ("killer_puuid", Utf8Array::<i32>::from_iter_values(events.iter().map(|e| e.killer_puuid.as_str())).boxed()),
+        ("victim_puuid", Utf8Array::<i32>::from_iter_values(events.iter().map(|e| e.victim_puuid.as_str())).boxed()),
+        ("weapon", Utf8Array::<i32>::from_iter(events.iter().map(|e| e.weapon.as_deref())).boxed()),
+        ("kill_category", Utf8Array::<i32>::from_iter_values(events.iter().map(|e| format!("{:?}", e.kill_category))).boxed()),
+        ("killer_x", Int32Array::from_iter(events.iter().map(|e| e.killer_location.as_ref().map(|l| l.x))).boxed()),
+        ("killer_y", Int32Array::from_iter(events.iter().map(|e| e.killer_location.as_ref().map(|l| l.y))).boxed()),
+        ("victim_x", Int32Array::from_iter(events.iter().map(|e| Some(e.victim_location.x))).boxed()),
+        ("victim_y", Int32Array::from_iter(events.iter().map(|e| Some(e.victim_location.y))).boxed()),
+        ("killer_u", Float64Array::from_iter(killer_uv.iter().map(|uv| uv.map(|(u, _)| u))).boxed()),
+        ("killer_v", Float64Array::from_iter(killer_uv.iter().map(|uv| uv.map(|(_, v)| v))).boxed()),
+        ("victim_u", Float64Array::from_iter(victim_uv.iter().map(|uv| uv.map(|(u, _)| u))).boxed()),
+        ("victim_v", Float64Array::from_iter(victim_uv.iter().map(|uv| uv.map(|(_, v)| v))).boxed()),
+        (
+            "distance",
+            Float64Array::from_iter(events.iter().map(|e| e.distance_reliable.then_some(e.distance))).boxed(),
+        ),
+    ];
+
+    let schema = Schema::from(
+        columns
+            .iter()
+            .map(|(name, array)| Field::new(*name, array.data_type().clone(), true))
+            .collect::<Vec<_>>(),
+    );
+    let chunk = Chunk::try_new(columns.into_iter().map(|(_, array)| array).collect()).map_err(parquet_err)?;
+
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V2,
+        data_pagesize_limit: None,
+    };
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|f| transverse(&f.data_type, |_| Encoding::Plain))
+        .collect();
+    let row_groups = RowGroupIterator::try_new(std::iter::once(Ok(chunk)), &schema, options, encodings)
+        .map_err(parquet_err)?;
+
+    let file = fs::File::create(output_path)?;
+    let mut writer = FileWriter::try_new(file, schema, options).map_err(parquet_err)?;
+    for group in row_groups {
+        writer.write(group.map_err(parquet_err)?).map_err(parquet_err)?;
+    }
+    writer.end(None).map_err(parquet_err)?;
+    Ok(())
+}
+
+/// Keep a map name safe to use in a file name
+fn file_name_part(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Render a killer-position heatmap PNG for every match into `output_dir`
+///
+/// Files are named `{match_id}_{map}.png`. A match that fails to load or render
+/// is counted in `failed` and the export carries on with the rest.
+pub fn export_all_heatmaps(
+    summaries: &[MatchSummary],
+    folder_path: &str,
+    output_dir: &Path,
+    grid_width: u32,
+    grid_height: u32,
+    colormap: Colormap,
+    progress_callback: impl Fn(usize, usize)
+) -> Result<BatchExportResult, ProcessorError> {
+    fs::create_dir_all(output_dir)?;
+
+    let mut result = BatchExportResult {
+        exported: 0,
+        failed: 0,
+        output_paths: Vec::new(),
+    };
+
+    for (i, summary) in summaries.iter().enumerate() {
+        let out_path = output_dir.join(format!(
+            "{}_{}.png",
+            file_name_part(&summary.match_id),
+            file_name_part(&summary.map_display)
+        ));
+
+        let rendered = json_processor::get_match_by_id(Some(folder_path), &summary.match_id)
+            .and_then(|detail| {
+                let heatmap = heatmap::compute_heatmap(&detail.map_display, &detail.kill_events, grid_width, grid_height);
+                render_counts_png(&heatmap.killer_counts, heatmap.grid_width, heatmap.grid_height, colormap, &out_path)
+            });
+
+        match rendered {
+            Ok(()) => {
+                result.exported += 1;
+                result.output_paths.push(out_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to export heatmap for {}: {}", summary.match_id, e);
+                result.failed += 1;
+            }
+        }
+
+        progress_callback(i + 1, summaries.len());
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heatmap_png_has_one_pixel_per_cell() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_path = dir.path().join("heatmap.png");
+        let cells = vec![
+            GridCell { x: 0, y: 0, weight: 1.0 },
+            GridCell { x: 7, y: 3, weight: 5.0 },
+        ];
+
+        render_heatmap_png(&cells, 8, out_path.to_str().unwrap()).unwrap();
+
+        let image = image::open(&out_path).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (8, 8));
+        assert_eq!(image.get_pixel(7, 3)[3], 255);
+        assert_eq!(image.get_pixel(1, 1)[3], 0);
+    }
+
+    #[test]
+    fn cells_outside_the_grid_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_path = dir.path().join("heatmap.png");
+
+        let result = render_heatmap_png(&[GridCell { x: 8, y: 0, weight: 1.0 }], 8, out_path.to_str().unwrap());
+
+        assert!(result.is_err());
+        assert!(!out_path.exists());
+    }
+
+    #[test]
+    fn parquet_export_reads_back() {
+        use arrow2::io::parquet::read;
+        use crate::test_support::*;
+
+        let dir = tempfile::tempdir().unwrap();
+        let out_path = dir.path().join("kills.parquet");
+        let events = kill_events(vec![
+            round(0, "Blue", vec![kill("b0", "r0", 1_000, (1000, -3000)), kill("b1", "r1", 2_000, (1000, -3000))]),
+            round(1, "Red", vec![with_weapon(kill("r2", "b2", 3_000, (1000, -3000)), OPERATOR)]),
+        ]);
+
+        export_kill_events_to_parquet(&events, "Ascent", &out_path).unwrap();
+
+        let mut file = fs::File::open(&out_path).unwrap();
+        let metadata = read::read_metadata(&mut file).unwrap();
+        let schema = read::infer_schema(&metadata).unwrap();
+        let weapon_column = schema.fields.iter().position(|f| f.name == "weapon").unwrap();
+        let chunks: Vec<_> = read::FileReader::new(file, metadata.row_groups, schema, None, None, None)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), 3);
+        let weapons = chunks[0].arrays()[weapon_column]
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .unwrap();
+        assert_eq!(weapons.value(2), "Operator");
+    }
+}
@@ -0,0 +1,316 @@
+use crate::coordinates;
+use crate::json_processor;
+use crate::models::*;
+use std::collections::HashMap;
+
+/// Index of the grid cell holding a minimap position, row-major from the top-left
+pub fn grid_cell_index(uv: (f64, f64), grid_width: u32, grid_height: u32) -> usize {
+    // u and v are clamped to [0, 1], so only the far edges need pulling back inside
+    let col = ((uv.0 * grid_width as f64) as u32).min(grid_width - 1);
+    let row = ((uv.1 * grid_height as f64) as u32).min(grid_height - 1);
+    (row * grid_width + col) as usize
+}
+
+/// Mirror a minimap position within the map's [0, 1] bounds
+pub fn flip_uv(uv: (f64, f64), flip: GridFlip) -> (f64, f64) {
+    (
+        if flip.flip_x { 1.0 - uv.0 } else { uv.0 },
+        if flip.flip_y { 1.0 - uv.1 } else { uv.1 },
+    )
+}
+
+/// Bin a match's killer and victim positions onto a grid over the minimap
+///
+/// Kills whose position can't be placed on the map are left out of that grid.
+/// Grid dimensions are raised to at least 1.
+pub fn compute_heatmap(map_display: &str, events: &[KillEvent], grid_width: u32, grid_height: u32) -> KillHeatmap {
+    let grid_width = grid_width.max(1);
+    let grid_height = grid_height.max(1);
+    let cell_count = (grid_width * grid_height) as usize;
+
+    let mut heatmap = KillHeatmap {
+        grid_width,
+        grid_height,
+        killer_counts: vec![0; cell_count],
+        victim_counts: vec![0; cell_count],
+    };
+
+    for event in events {
+        if let Some(uv) = event.killer_location.as_ref().and_then(|l| coordinates::to_uv(l, map_display)) {
+            heatmap.killer_counts[grid_cell_index(uv, grid_width, grid_height)] += 1;
+        }
+        if let Some(uv) = coordinates::to_uv(&event.victim_location, map_display) {
+            heatmap.victim_counts[grid_cell_index(uv, grid_width, grid_height)] += 1;
+        }
+    }
+
+    heatmap
+}
+
+/// Counts scaled so the busiest cell is 1; an empty grid stays all zero
+fn max_normalize(counts: &[u32]) -> Vec<f32> {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+    counts.iter().map(|&count| count as f32 / max).collect()
+}
+
+/// Combine killer and victim grids into one danger-zone grid
+///
+/// Each grid is max-normalized on its own before weighting, so a match with far
+/// more victim than killer positions doesn't drown out the killer layer.
+pub fn blend_heatmap(heatmap: &KillHeatmap, killer_weight: f32, victim_weight: f32) -> Vec<f32> {
+    max_normalize(&heatmap.killer_counts)
+        .into_iter()
+        .zip(max_normalize(&heatmap.victim_counts))
+        .map(|(killer, victim)| killer_weight * killer + victim_weight * victim)
+        .collect()
+}
+
+/// Flatten a heatmap to a single layer for the given blend mode
+pub fn blend_heatmap_mode(heatmap: &KillHeatmap, mode: BlendMode) -> BlendedHeatmap {
+    let (killer_weight, victim_weight) = match mode {
+        BlendMode::Killer => (1.0, 0.0),
+        BlendMode::Victim => (0.0, 1.0),
+        BlendMode::Blended => (0.5, 0.5),
+    };
+
+    BlendedHeatmap {
+        grid_width: heatmap.grid_width,
+        grid_height: heatmap.grid_height,
+        intensities: blend_heatmap(heatmap, killer_weight, victim_weight),
+    }
+}
+
+/// Where kills (victim positions) increased or decreased going from `a` to `b`
+///
+/// Both heatmaps must share the same grid size; cells past the shorter grid are ignored.
+pub fn diff_heatmaps(a: &KillHeatmap, b: &KillHeatmap) -> DiffHeatmap {
+    let (positive, negative) = a.victim_counts
+        .iter()
+        .zip(&b.victim_counts)
+        .map(|(&before, &after)| {
+            let change = after as f32 - before as f32;
+            (change.max(0.0), (-change).max(0.0))
+        })
+        .unzip();
+
+    DiffHeatmap {
+        grid_width: a.grid_width,
+        grid_height: a.grid_height,
+        positive,
+        negative,
+    }
+}
+
+/// The `n` cells with the most victims, busiest first
+///
+/// Empty cells are never returned, so fewer than `n` positions may come back.
+/// Ties keep grid order.
+pub fn top_danger_positions(heatmap: &KillHeatmap, n: usize) -> Vec<DangerPosition> {
+    let mut cells: Vec<(usize, u32)> = heatmap.victim_counts
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    cells.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    cells
+        .into_iter()
+        .take(n)
+        .map(|(i, victim_count)| {
+            let col = i as u32 % heatmap.grid_width;
+            let row = i as u32 / heatmap.grid_width;
+            DangerPosition {
+                uv_x: (col as f64 + 0.5) / heatmap.grid_width as f64,
+                uv_y: (row as f64 + 0.5) / heatmap.grid_height as f64,
+                victim_count,
+                cell_index: i as u32,
+            }
+        })
+        .collect()
+}
+
+/// Non-empty cells of a row-major weight grid
+pub fn grid_cells(weights: &[f64], grid_width: u32) -> Vec<GridCell> {
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, &weight)| weight > 0.0)
+        .map(|(i, &weight)| GridCell {
+            x: i as u32 % grid_width,
+            y: i as u32 / grid_width,
+            weight,
+        })
+        .collect()
+}
+
+/// Per-match weights rising linearly with start time, from 1/n for the oldest to 1
+///
+/// Matches without a start time are treated as the oldest; ties keep input order.
+fn recency_weights(details: &[MatchDetail]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..details.len()).collect();
+    order.sort_by_key(|&i| details[i].game_start);
+
+    let mut weights = vec![0.0; details.len()];
+    for (rank, i) in order.into_iter().enumerate() {
+        weights[i] = (rank + 1) as f64 / details.len() as f64;
+    }
+    weights
+}
+
+/// Killer positions from several matches binned onto one square grid
+///
+/// Each match is placed using its own minimap transform, so matches on different
+/// maps share the grid but not the layout. `mode` decides how much each kill adds
+/// to its cell, and `flip` mirrors positions to match the minimap image's orientation.
+pub fn aggregate_kill_cells(details: &[MatchDetail], grid_size: u32, mode: WeightMode, flip: GridFlip) -> Vec<GridCell> {
+    let grid_size = grid_size.max(1);
+    let mut weights = vec![0.0; (grid_size * grid_size) as usize];
+
+    let match_weights = match mode {
+        WeightMode::RecencyLinear => recency_weights(details),
+        WeightMode::Uniform | WeightMode::OpeningOnly => vec![1.0; details.len()],
+    };
+
+    for (detail, match_weight) in details.iter().zip(match_weights) {
+        let events: Vec<&KillEvent> = match mode {
+            WeightMode::OpeningOnly => json_processor::extract_opening_duels(&detail.kill_events),
+            WeightMode::Uniform | WeightMode::RecencyLinear => detail.kill_events.iter().collect(),
+        };
+
+        for event in events {
+            if let Some(uv) = event.killer_location.as_ref().and_then(|l| coordinates::to_uv(l, &detail.map_display)) {
+                weights[grid_cell_index(flip_uv(uv, flip), grid_size, grid_size)] += match_weight;
+            }
+        }
+    }
+
+    grid_cells(&weights, grid_size)
+}
+
+/// Every player position snapshotted at kill time, binned onto one square grid
+///
+/// Each kill records where all alive players stood, so this approximates where
+/// players spend their time. Pass a PUUID to only count that player.
+pub fn aggregate_player_positions(matches: &[MatchRounds], puuid: Option<&str>, grid_size: u32, flip: GridFlip) -> Vec<GridCell> {
+    let grid_size = grid_size.max(1);
+    let mut counts = vec![0.0; (grid_size * grid_size) as usize];
+
+    for rounds in matches {
+        let (kills, _) = json_processor::extract_kills(&rounds.round_results, &HashMap::new());
+        let positions = kills
+            .iter()
+            .flat_map(|(kill, _)| &kill.player_locations)
+            .filter(|pl| puuid.is_none_or(|puuid| pl.puuid == puuid));
+
+        for pl in positions {
+            if let Some(uv) = coordinates::to_uv(&pl.location, &rounds.map_display) {
+                counts[grid_cell_index(flip_uv(uv, flip), grid_size, grid_size)] += 1.0;
+            }
+        }
+    }
+
+    grid_cells(&counts, grid_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    fn located_match() -> MatchRounds {
+        let mut first = with_killer_at(kill("b0", "r0", 1_000, (1000, -3000)), (1000, -3000));
+        first["playerLocations"].as_array_mut().unwrap().push(serde_json::json!({ "puuid": "b1", "location": { "x": 4000, "y": -9000 } }));
+        let second = with_killer_at(kill("b0", "r1", 2_000, (1000, -3000)), (1000, -3000));
+        let third = with_killer_at(kill("b1", "r2", 3_000, (1000, -3000)), (4000, -9000));
+
+        MatchRounds {
+            map_display: "Ascent".to_string(),
+            round_results: match_data(&vct_match("positions", ten_players(), vec![round(0, "Blue", vec![first, second, third])])).round_results,
+        }
+    }
+
+    #[test]
+    fn every_player_location_is_binned() {
+        let cells = aggregate_player_positions(&[located_match()], None, 16, GridFlip::default());
+
+        assert_eq!(cells.iter().map(|c| c.weight).sum::<f64>(), 4.0);
+        assert_eq!(cells.len(), 2);
+    }
+
+    #[test]
+    fn positions_can_be_limited_to_one_player() {
+        let cells = aggregate_player_positions(&[located_match()], Some("b0"), 16, GridFlip::default());
+
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].weight, 2.0);
+    }
+
+    #[test]
+    fn unlocated_killers_are_left_off_the_killer_grid() {
+        let mut unlocated = kill("b1", "r1", 2_000, (1000, -3000));
+        unlocated["playerLocations"] = serde_json::json!([{ "puuid": "r1", "location": { "x": 1000, "y": -3000 } }]);
+        let events = kill_events(vec![round(0, "Blue", vec![
+            with_killer_at(kill("b0", "r0", 1_000, (1000, -3000)), (1500, -3500)),
+            unlocated,
+        ])]);
+
+        let unresolved = events.iter().find(|e| e.killer_puuid == "b1").unwrap();
+        assert!(unresolved.killer_location.is_none());
+
+        let heatmap = compute_heatmap("Ascent", &events, 8, 8);
+        assert_eq!(heatmap.killer_counts.iter().sum::<u32>(), 1);
+        assert_eq!(heatmap.victim_counts.iter().sum::<u32>(), 2);
+    }
+
+    fn located_details() -> Vec<MatchDetail> {
+        let located = |killer: &str, victim: &str, time: i32, at: (i32, i32)| with_killer_at(kill(killer, victim, time, at), at);
+        let first = vct_match("first", ten_players(), vec![
+            round(0, "Blue", vec![located("b0", "r0", 1_000, (1000, -3000)), located("b1", "r1", 2_000, (4000, -9000))]),
+            round(1, "Red", vec![located("r0", "b0", 1_000, (4000, -9000)), located("r1", "b1", 2_000, (4000, -9000)), located("r2", "b2", 3_000, (1000, -3000))]),
+        ]);
+        let second = vct_match("second", ten_players(), vec![
+            round(0, "Blue", vec![located("b2", "r2", 1_000, (1000, -3000)), located("b3", "r3", 2_000, (1000, -3000))]),
+        ]);
+        vec![parse_detail(&first), parse_detail(&second)]
+    }
+
+    fn total_weight(cells: &[GridCell]) -> f64 {
+        cells.iter().map(|c| c.weight).sum()
+    }
+
+    #[test]
+    fn uniform_weights_are_raw_kill_counts() {
+        let details = located_details();
+        let cells = aggregate_kill_cells(&details, 16, WeightMode::Uniform, GridFlip::default());
+
+        let raw: usize = details.iter().map(|d| d.kill_events.len()).sum();
+        assert_eq!(total_weight(&cells), raw as f64);
+        assert!(cells.iter().all(|c| c.weight.fract() == 0.0));
+    }
+
+    #[test]
+    fn opening_only_counts_match_the_opening_duels() {
+        let details = located_details();
+        let cells = aggregate_kill_cells(&details, 16, WeightMode::OpeningOnly, GridFlip::default());
+
+        let openings: usize = details.iter().map(|d| json_processor::extract_opening_duels(&d.kill_events).len()).sum();
+        assert_eq!(openings, 3);
+        assert_eq!(total_weight(&cells), openings as f64);
+    }
+
+    #[test]
+    fn flipping_y_reflects_the_row() {
+        let grid_size = 16;
+        let flip_y = GridFlip { flip_x: false, flip_y: true };
+        let plain = aggregate_player_positions(&[located_match()], Some("b0"), grid_size, GridFlip::default());
+        let flipped = aggregate_player_positions(&[located_match()], Some("b0"), grid_size, flip_y);
+
+        assert_eq!(plain.len(), 1);
+        assert_eq!(flipped.len(), 1);
+        assert_eq!(flipped[0].x, plain[0].x);
+        assert_eq!(flipped[0].y, grid_size - 1 - plain[0].y);
+        assert_ne!(flipped[0].y, plain[0].y);
+        assert_eq!(flipped[0].weight, plain[0].weight);
+    }
+}
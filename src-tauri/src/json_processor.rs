@@ -1,8 +1,19 @@
 use crate::models::*;
-use chrono::{Utc, TimeZone};
+use crate::error::ProcessorError;
+use crate::summary_db::SummaryDb;
+use crate::utils;
+use chrono::{DateTime, Utc, TimeZone};
+use flate2::read::GzDecoder;
+use lru::LruCache;
+use rayon::prelude::*;
 use std::collections::HashMap;
-use std::fs;
+use serde::de::DeserializeOwned;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
 use walkdir::WalkDir;
 
 /// Weapon UUID to name mapping (from reference code)
@@ -49,6 +60,94 @@ fn get_weapon_map() -> HashMap<&'static str, &'static str> {
     map
 }
 
+/// Internal map code names to display names
+fn get_map_names() -> HashMap<&'static str, &'static str> {
+    let mut map = HashMap::new();
+
+    map.insert("Ascent", "Ascent");
+    map.insert("Bonsai", "Split");
+    map.insert("Duality", "Bind");
+    map.insert("Triad", "Haven");
+    map.insert("Port", "Icebox");
+    map.insert("Foxtrot", "Breeze");
+    map.insert("Canyon", "Fracture");
+    map.insert("Pitt", "Pearl");
+    map.insert("Jam", "Lotus");
+    map.insert("Juliett", "Sunset");
+    map.insert("Infinity", "Abyss");
+    map.insert("Rook", "Corrode");
+    map.insert("Range", "The Range");
+
+    map
+}
+
+/// Agent (character) UUID to display name mapping
+fn get_agent_names() -> HashMap<&'static str, &'static str> {
+    let mut map = HashMap::new();
+
+    // Duelists
+    map.insert("ADD6443A-41BD-E414-F6AD-E58D267F4E95", "Jett");
+    map.insert("A3BFB853-43B2-7238-A4F1-AD90E9E46BCC", "Reyna");
+    map.insert("EB93336A-449B-9C1B-0A54-A891F7921D69", "Phoenix");
+    map.insert("F94C3B30-42BE-E959-889C-5AA313DBA261", "Raze");
+    map.insert("7F94D92C-4234-0A36-9646-3A87EB8B5C89", "Yoru");
+    map.insert("BB2A4828-46EB-8CD1-E765-15848195D751", "Neon");
+    map.insert("0E38B510-41A8-5780-5E8F-568B2A4F2D6C", "Iso");
+
+    // Initiators
+    map.insert("320B2A48-4D9B-A075-30F1-1F93A9B638FA", "Sova");
+    map.insert("5F8D3A7F-467B-97F3-062C-13ACF203C006", "Breach");
+    map.insert("6F2A04CA-43E0-BE17-7F36-B3908627744D", "Skye");
+    map.insert("601DBBE7-43CE-BE57-2A40-4ABD24953621", "KAY/O");
+    map.insert("DADE69B4-4F5A-8528-247B-219E5A1FACD6", "Fade");
+    map.insert("E370FA57-4757-3604-3648-499E1F642D3F", "Gekko");
+
+    // Controllers
+    map.insert("9F0D8BA9-4140-B941-57D3-A7AD57C6B417", "Brimstone");
+    map.insert("8E253930-4C05-31DD-1B6C-968525494517", "Omen");
+    map.insert("707EAB51-4836-F488-046A-CDA6BF494859", "Viper");
+    map.insert("41FB69C1-4189-7B37-F117-BCAF1E96F1BF", "Astra");
+    map.insert("95B78ED7-4637-86D9-7E41-71BA8C293152", "Harbor");
+    map.insert("1DBF2EDD-4729-0984-3115-DAA5EED44993", "Clove");
+
+    // Sentinels
+    map.insert("569FDD95-4D10-43AB-CA70-79BECC718B46", "Sage");
+    map.insert("1E58DE9C-4950-5125-93E9-A0AEE9F98746", "Killjoy");
+    map.insert("117ED9E3-49F3-6512-3CCF-0CADA7E3823B", "Cypher");
+    map.insert("22697A3D-45BF-8DD7-4FEC-84A9E28C69D7", "Chamber");
+    map.insert("CC8B64C8-4B25-4FF9-6E7F-37B4DA43D235", "Deadlock");
+
+    map
+}
+
+/// Display name for an agent UUID, or None when the UUID isn't in the agent table
+pub fn known_agent_name(character_id: &str) -> Option<&'static str> {
+    get_agent_names().get(character_id.to_uppercase().as_str()).copied()
+}
+
+/// Display name for an agent UUID, falling back to the UUID itself when unknown
+pub fn agent_display_name(character_id: &str) -> String {
+    known_agent_name(character_id)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| character_id.to_string())
+}
+
+/// Turn an internal map path like `/Game/Maps/Bonsai/Bonsai` into its display name
+///
+/// Unknown maps fall back to the last path segment.
+pub fn normalize_map_name(raw: &str) -> String {
+    let last_segment = raw
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(raw);
+
+    get_map_names()
+        .get(last_segment)
+        .map(|&name| name.to_string())
+        .unwrap_or_else(|| last_segment.to_string())
+}
+
 /// Extract region from file path
 fn extract_region_from_path(path: &Path) -> String {
     let path_str = path.to_string_lossy();
@@ -66,8 +165,148 @@ fn extract_region_from_path(path: &Path) -> String {
     }
 }
 
+/// Regulation is 24 rounds split into two halves of 12; anything after is overtime
+pub fn round_phase(round_num: i32) -> RoundPhase {
+    match round_num {
+        ..=11 => RoundPhase::FirstHalf,
+        12..=23 => RoundPhase::SecondHalf,
+        _ => RoundPhase::Overtime,
+    }
+}
+
+/// Positions a kill contributes to a map from the given perspective
+///
+/// Kills and deaths give one position each; assists give one per assistant listed
+/// in the raw kill's player locations.
+pub fn perspective_locations<'a>(kill: &'a Kill, event: &'a KillEvent, perspective: Perspective) -> Vec<&'a Location> {
+    match perspective {
+        Perspective::Kills => event.killer_location.iter().collect(),
+        Perspective::Deaths => vec![&event.victim_location],
+        Perspective::Assists => kill.assistants
+            .iter()
+            .filter_map(|assistant| kill.player_locations.iter().find(|pl| &pl.puuid == assistant))
+            .map(|pl| &pl.location)
+            .collect(),
+    }
+}
+
+/// Kills from a single round, in event order; empty if the round had none
+pub fn round_kills(events: &[KillEvent], round_num: i32) -> Vec<KillEvent> {
+    events
+        .iter()
+        .filter(|e| e.round_num == round_num)
+        .cloned()
+        .collect()
+}
+
+/// Copy of a match detail keeping only the kills from the given phase
+pub fn filter_detail_by_phase(detail: MatchDetail, phase: Option<RoundPhase>) -> MatchDetail {
+    match phase {
+        Some(phase) => MatchDetail {
+            kill_events: detail.kill_events
+                .into_iter()
+                .filter(|e| e.round_phase == phase)
+                .collect(),
+            ..detail
+        },
+        None => detail,
+    }
+}
+
+/// Classify a kill by its finishing damage
+///
+/// The export's damage type is used when present; otherwise the resolved
+/// weapon name decides, with any recognized gun counting as `Gun`.
+fn kill_category(finishing_damage: Option<&FinishingDamage>, weapon_name: Option<&str>) -> KillCategory {
+    let damage_type = finishing_damage.and_then(|fd| fd.damage_type.as_deref());
+
+    match (damage_type, weapon_name) {
+        (Some("Bomb"), _) => KillCategory::Bomb,
+        (Some("Fall"), _) => KillCategory::Fall,
+        (Some("Ability"), _) | (_, Some("Ability")) => KillCategory::Ability,
+        (Some("Melee"), _) | (_, Some("Knife" | "Melee")) => KillCategory::Melee,
+        (Some("Weapon"), _) | (_, Some(_)) => KillCategory::Gun,
+        _ => KillCategory::Unknown,
+    }
+}
+
+/// Default for [`set_max_coordinate_magnitude`], well past the edge of every map
+pub const DEFAULT_MAX_COORDINATE_MAGNITUDE: i32 = 100_000;
+
+/// Largest raw x or y coordinate a kill may have before it is treated as corrupt
+static MAX_COORDINATE_MAGNITUDE: AtomicI32 = AtomicI32::new(DEFAULT_MAX_COORDINATE_MAGNITUDE);
+
+/// Change the coordinate magnitude past which kills are dropped when parsing matches
+///
+/// Cached match details were parsed with the old limit, so they are dropped when it changes.
+pub fn set_max_coordinate_magnitude(magnitude: i32) {
+    if MAX_COORDINATE_MAGNITUDE.swap(magnitude, Ordering::Relaxed) != magnitude {
+        clear_match_cache();
+    }
+}
+
+fn is_plausible_location(location: &Location, max_magnitude: i32) -> bool {
+    location.x.unsigned_abs() <= max_magnitude.unsigned_abs() && location.y.unsigned_abs() <= max_magnitude.unsigned_abs()
+}
+
+/// Drop kills whose killer or victim position is beyond `max_magnitude` on either axis
+///
+/// Corrupt exports sometimes carry coordinates in the millions, which would otherwise
+/// stretch bounds-based normalization. Returns the kept events and how many were dropped.
+fn sanitize_locations(kills: Vec<(&Kill, KillEvent)>, max_magnitude: i32) -> (Vec<(&Kill, KillEvent)>, u32) {
+    let total = kills.len();
+    let kept: Vec<(&Kill, KillEvent)> = kills
+        .into_iter()
+        .filter(|(_, e)| {
+            is_plausible_location(&e.victim_location, max_magnitude)
+                && e.killer_location.as_ref().is_none_or(|l| is_plausible_location(l, max_magnitude))
+        })
+        .collect();
+
+    let dropped = (total - kept.len()) as u32;
+    (kept, dropped)
+}
+
+/// Classify each round as an execute, a retake or a round without a plant
+///
+/// The planter's team is taken from `player_teams`. A planted round only counts
+/// as an execute when that team is known to have won it, so planted rounds with
+/// an unknown winner or planter are reported as retakes.
+pub fn classify_rounds(round_results: &[RoundResult], player_teams: &HashMap<&str, &str>) -> Vec<RoundType> {
+    round_results
+        .iter()
+        .map(|round| {
+            let Some(planter) = round.bomb_planter.as_deref() else {
+                return RoundType::NoPlant;
+            };
+
+            let attackers_won = player_teams
+                .get(planter)
+                .is_some_and(|&team| round.winning_team.as_deref() == Some(team));
+            if attackers_won {
+                RoundType::Execute
+            } else {
+                RoundType::Retake
+            }
+        })
+        .collect()
+}
+
 /// Extract kill events from round results
-fn extract_kill_events(round_results: &[RoundResult]) -> Vec<KillEvent> {
+///
+/// `player_teams` maps PUUIDs to team IDs so each kill can be marked with
+/// whether the killer's team went on to win the round. Kills with implausible
+/// coordinates are left out and counted in the second value.
+pub fn extract_kill_events(round_results: &[RoundResult], player_teams: &HashMap<&str, &str>) -> (Vec<KillEvent>, u32) {
+    let (kills, dropped) = extract_kills(round_results, player_teams);
+    (kills.into_iter().map(|(_, event)| event).collect(), dropped)
+}
+
+/// [`extract_kill_events`], keeping each event paired with the raw kill it came from
+pub fn extract_kills<'a>(
+    round_results: &'a [RoundResult],
+    player_teams: &HashMap<&str, &str>
+) -> (Vec<(&'a Kill, KillEvent)>, u32) {
     let weapon_map = get_weapon_map();
     let mut kill_events = Vec::new();
     
@@ -89,31 +328,55 @@ fn extract_kill_events(round_results: &[RoundResult]) -> Vec<KillEvent> {
                     None => continue,
                 };
                 
-                // Find killer location from playerLocations
+                // Find killer location from playerLocations; None when the killer isn't listed
                 let killer_loc = kill.player_locations
                     .iter()
                     .find(|pl| pl.puuid == kill.killer)
-                    .map(|pl| pl.location.clone())
-                    .unwrap_or(Location { x: 0, y: 0 });
+                    .map(|pl| pl.location.clone());
+                let distance = killer_loc.as_ref().map_or(0.0, |killer| {
+                    (killer.x as f64 - victim_loc.x as f64).hypot(killer.y as f64 - victim_loc.y as f64)
+                });
+                let distance_reliable = killer_loc.is_some();
                 
-                kill_events.push(KillEvent {
+                kill_events.push((kill, KillEvent {
                     killer_puuid: kill.killer.clone(),
                     victim_puuid: kill.victim.clone(),
+                    kill_category: kill_category(kill.finishing_damage.as_ref(), weapon_name.as_deref()),
                     weapon: weapon_name,
                     killer_location: killer_loc,
                     victim_location: victim_loc,
+                    distance,
+                    distance_reliable,
+                    assistants: kill.assistants.clone(),
+                    round_won_by_killer: player_teams
+                        .get(kill.killer.as_str())
+                        .zip(round_data.winning_team.as_deref())
+                        .map(|(&team, winner)| team == winner),
                     round_num,
+                    round_phase: round_phase(round_num),
                     round_time_millis: kill.time_since_round_start_millis,
-                });
+                    game_time_millis: kill.time_since_game_start_millis,
+                }));
             }
         }
     }
     
-    kill_events
+    sanitize_locations(kill_events, MAX_COORDINATE_MAGNITUDE.load(Ordering::Relaxed))
+}
+
+/// Start time from epoch milliseconds, or None for zero, negative or out-of-range values
+///
+/// Bad timestamps used to fall back to the current time, which pushed broken
+/// matches to the top of date-sorted lists.
+fn parse_game_start(millis: i64) -> Option<DateTime<Utc>> {
+    if millis <= 0 {
+        return None;
+    }
+    Utc.timestamp_millis_opt(millis).single()
 }
 
 /// Parse match JSON file into MatchSummary
-pub fn parse_match_summary(path: &Path, data: &VctMatchData) -> MatchSummary {
+pub fn parse_match_summary(path: &Path, data: &VctMatchSummaryData) -> MatchSummary {
     let region = extract_region_from_path(path);
     
     // Extract unique teams
@@ -126,14 +389,22 @@ pub fn parse_match_summary(path: &Path, data: &VctMatchData) -> MatchSummary {
         }
     }
     
-    // Calculate score from round results
+    // Count players per team, leaving out observers and coaches
+    let mut team_sizes: HashMap<String, u32> = HashMap::new();
+    for player in data.players.iter().filter(|p| p.team_id == "Blue" || p.team_id == "Red") {
+        *team_sizes.entry(player.team_id.clone()).or_insert(0) += 1;
+    }
+    
+    // Calculate score from round results, ignoring rounds credited to anything but Blue or Red
     let mut team_wins: HashMap<String, i32> = HashMap::new();
     team_wins.insert("Blue".to_string(), 0);
     team_wins.insert("Red".to_string(), 0);
+    let mut rounds_counted = 0;
     
     for round_result in &data.round_results {
-        if let Some(winning_team) = &round_result.winning_team {
-            *team_wins.entry(winning_team.clone()).or_insert(0) += 1;
+        if let Some(wins) = round_result.winning_team.as_ref().and_then(|team| team_wins.get_mut(team)) {
+            *wins += 1;
+            rounds_counted += 1;
         }
     }
     
@@ -143,18 +414,46 @@ pub fn parse_match_summary(path: &Path, data: &VctMatchData) -> MatchSummary {
         team_wins.get("Red").unwrap_or(&0)
     );
     
-    let game_start = Utc.timestamp_millis_opt(data.match_info.game_start_millis)
-        .single()
-        .unwrap_or_else(|| Utc::now());
+    let game_start = parse_game_start(data.match_info.game_start_millis);
     
+    // Caster/observer-only records have no Blue or Red players, so their score is meaningless
+    let is_valid_match = !teams.is_empty();
+
     MatchSummary {
         match_id: data.match_info.match_id.clone(),
         map: data.match_info.map.clone(),
+        map_display: normalize_map_name(&data.match_info.map),
         region,
         game_start,
+        game_start_valid: game_start.is_some(),
         teams,
+        team_sizes,
         score,
+        rounds_counted,
+        game_length_millis: data.match_info.game_length_millis,
+        game_length_display: data.match_info.game_length_millis.map(utils::format_game_duration),
+        queue: data.match_info.queue_id.clone(),
+        game_version: data.match_info.game_version.clone(),
+        is_valid_match,
+    }
+}
+
+/// Display name for each team, from the export's team data where it names the organization
+///
+/// Blue and Red are always present; other team IDs only appear when the export names them.
+pub fn team_display_names(team_data: Option<&[VctTeamData]>) -> HashMap<String, String> {
+    let mut names: HashMap<String, String> = ["Blue", "Red"]
+        .into_iter()
+        .map(|team| (team.to_string(), team.to_string()))
+        .collect();
+
+    for team in team_data.unwrap_or_default() {
+        if let Some(name) = team.name.as_deref().map(str::trim).filter(|name| !name.is_empty()) {
+            names.insert(team.team_id.clone(), name.to_string());
+        }
     }
+
+    names
 }
 
 /// Parse match JSON file into MatchDetail
@@ -186,224 +485,3374 @@ pub fn parse_match_detail(path: &Path, data: &VctMatchData) -> MatchDetail {
         .collect();
     
     // Extract kill events
-    let kill_events = extract_kill_events(&data.round_results);
+    let player_teams: HashMap<&str, &str> = data.players
+        .iter()
+        .map(|p| (p.puuid.as_str(), p.team_id.as_str()))
+        .collect();
+    let (kill_events, dropped_kill_events) = extract_kill_events(&data.round_results, &player_teams);
     
-    let game_start = Utc.timestamp_millis_opt(data.match_info.game_start_millis)
-        .single()
-        .unwrap_or_else(|| Utc::now());
+    let game_start = parse_game_start(data.match_info.game_start_millis);
     
-    MatchDetail {
+    let mut detail = MatchDetail {
         match_id: data.match_info.match_id.clone(),
         map: data.match_info.map.clone(),
+        map_display: normalize_map_name(&data.match_info.map),
         region,
         game_start,
+        game_start_valid: game_start.is_some(),
         game_length_millis: data.match_info.game_length_millis,
+        game_length_formatted: data.match_info.game_length_millis
+            .map(utils::format_game_duration)
+            .unwrap_or_else(|| "Unknown".to_string()),
         rounds_played: data.round_results.len() as i32,
-        winning_team: "Unknown".to_string(), // Could calculate from round results
+        overtime_rounds: data.round_results
+            .iter()
+            .filter(|r| round_phase(r.round_num) == RoundPhase::Overtime)
+            .count() as u32,
+        winning_team: "Unknown".to_string(),
+        mvp_puuid: mvp_player(&players).map(|p| p.puuid.clone()),
         players,
         kill_events,
+        dropped_kill_events,
+        team_display_names: team_display_names(data.match_info.team_data.as_deref()),
+        round_types: classify_rounds(&data.round_results, &player_teams),
+        rounds: data.round_results
+            .iter()
+            .map(|round| RoundOutcome {
+                round_num: round.round_num,
+                winning_team: round.winning_team.clone(),
+                round_phase: round_phase(round.round_num),
+                plant: round.bomb_planter.clone().map(|player_puuid| SpikeEvent {
+                    player_puuid,
+                    round_time_millis: round.plant_round_time.unwrap_or(0),
+                    location: round.plant_location.clone(),
+                    site: round.plant_site.clone().filter(|site| !site.is_empty()),
+                }),
+                defuse: round.bomb_defuser.clone().map(|player_puuid| SpikeEvent {
+                    player_puuid,
+                    round_time_millis: round.defuse_round_time.unwrap_or(0),
+                    location: round.defuse_location.clone(),
+                    site: None,
+                }),
+            })
+            .collect(),
+    };
+
+    // Draws and matches without round data stay "Unknown"
+    if let Some(winner) = map_winner(&detail) {
+        detail.winning_team = winner;
     }
+    detail
 }
 
-/// Load all JSON files from a directory with progress tracking
-pub fn load_json_files_with_progress(folder_path: &str, progress_callback: impl Fn(usize, usize)) -> Result<Vec<MatchSummary>, String> {
-    let path = Path::new(folder_path);
+/// Check that raw JSON has the structure of a VCT match before deserializing it
+pub fn validate_match_json(raw: &str) -> Result<(), Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(raw)
+        .map_err(|e| vec![format!("Invalid JSON: {}", e)])?;
 
-    if !path.exists() {
-        return Err(format!("Folder does not exist: {}", folder_path));
+    validate_match_value(&value)
+}
+
+fn validate_match_value(value: &serde_json::Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    let root = match value.as_object() {
+        Some(root) => root,
+        None => return Err(vec!["Top-level value is not an object".to_string()]),
+    };
+
+    match root.get("matchInfo") {
+        None => errors.push("Missing field: matchInfo".to_string()),
+        Some(info) if !info.is_object() => errors.push("matchInfo is not an object".to_string()),
+        Some(info) => match info.get("matchId") {
+            None => errors.push("Missing field: matchInfo.matchId".to_string()),
+            Some(id) => match id.as_str() {
+                None => errors.push("matchInfo.matchId is not a string".to_string()),
+                Some("") => errors.push("matchInfo.matchId is empty".to_string()),
+                Some(_) => {}
+            },
+        },
     }
 
-    let mut all_files = Vec::new();
+    match root.get("players") {
+        None => errors.push("Missing field: players".to_string()),
+        Some(players) => match players.as_array() {
+            None => errors.push("players is not an array".to_string()),
+            Some(players) if players.is_empty() => errors.push("players array is empty".to_string()),
+            Some(_) => {}
+        },
+    }
 
-    // Collect all JSON file paths first
-    for entry in WalkDir::new(path)
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
+    match root.get("roundResults") {
+        None => errors.push("Missing field: roundResults".to_string()),
+        Some(rounds) if !rounds.is_array() => errors.push("roundResults is not an array".to_string()),
+        Some(_) => {}
+    }
 
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            all_files.push(path.to_path_buf());
-        }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
     }
+}
 
-    let total_files = all_files.len();
-    let mut matches = Vec::new();
-    let mut processed = 0;
-
-    // Process all files with progress updates
-    for file_path in &all_files {
-        match fs::read_to_string(file_path) {
-            Ok(content) => {
-                match serde_json::from_str::<VctMatchData>(&content) {
-                    Ok(data) => {
-                        let summary = parse_match_summary(file_path, &data);
-                        matches.push(summary);
-                    }
-                    Err(e) => {
-                        eprintln!("Error parsing {}: {}", file_path.display(), e);
-                        // Continue processing other files even if one fails
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Error reading {}: {}", file_path.display(), e);
-                // Continue processing other files even if one fails
-            }
-        }
+/// Validate and deserialize a match file's contents
+pub fn parse_match_json(raw: &str) -> Result<VctMatchData, ProcessorError> {
+    let value: serde_json::Value = serde_json::from_str(raw)?;
 
-        processed += 1;
+    validate_match_value(&value).map_err(|errors| ProcessorError::InvalidData(errors.join("; ")))?;
 
-        // Report progress every 10 files or at key milestones
-        if processed % 10 == 0 || processed == total_files || processed == 1 {
-            progress_callback(processed, total_files);
-        }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Top-level match data the loaders can deserialize, either full or summary-only
+pub trait MatchData: DeserializeOwned {
+    fn match_info(&self) -> &MatchInfo;
+    fn players(&self) -> &[VctPlayer];
+}
+
+impl MatchData for VctMatchData {
+    fn match_info(&self) -> &MatchInfo {
+        &self.match_info
     }
 
-    Ok(matches)
+    fn players(&self) -> &[VctPlayer] {
+        &self.players
+    }
 }
 
-/// Index of match IDs to file paths for fast lookup
-static mut MATCH_INDEX: Option<HashMap<String, PathBuf>> = None;
+impl MatchData for VctMatchSummaryData {
+    fn match_info(&self) -> &MatchInfo {
+        &self.match_info
+    }
 
-/// Load all JSON files from a directory and build index
-pub fn load_json_files(folder_path: &str) -> Result<Vec<MatchSummary>, String> {
-    let matches = load_json_files_with_progress(folder_path, |_, _| {})?;
+    fn players(&self) -> &[VctPlayer] {
+        &self.players
+    }
+}
 
-    // Build index for fast lookups
-    let mut index = HashMap::new();
-    let path = Path::new(folder_path);
+/// Check the parts of already-deserialized match data that serde can't enforce
+fn validate_match_data(data: &impl MatchData) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
 
-    for entry in WalkDir::new(path)
-        .follow_links(true)
+    if data.match_info().match_id.is_empty() {
+        errors.push("matchInfo.matchId is empty".to_string());
+    }
+    if data.players().is_empty() {
+        errors.push("players array is empty".to_string());
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Deserialize match data in a single pass over a reader, without buffering the file as a String
+///
+/// Reading into a String first held the raw file alongside the parsed data, so peak memory per
+/// file was roughly file size plus parsed size. Streaming keeps only the 8 KiB `BufReader`
+/// buffer next to the parsed data, which matters when many large files load in parallel.
+fn read_match_data<T: MatchData>(reader: impl Read) -> Result<T, ProcessorError> {
+    let mut de = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    let data = T::deserialize(&mut de)?;
+    de.end()?;
+
+    validate_match_data(&data).map_err(|errors| ProcessorError::InvalidData(errors.join("; ")))?;
+
+    Ok(data)
+}
+
+/// Parse a match summary directly from a reader
+pub fn parse_match_summary_from_reader(path: &Path, reader: impl Read) -> Result<MatchSummary, ProcessorError> {
+    read_match_data(reader).map(|data| parse_match_summary(path, &data))
+}
+
+/// Parse a match detail directly from a reader
+pub fn parse_match_detail_from_reader(path: &Path, reader: impl Read) -> Result<MatchDetail, ProcessorError> {
+    read_match_data(reader).map(|data| parse_match_detail(path, &data))
+}
+
+/// Load all JSON files from a directory with progress tracking
+///
+/// Files are streamed through a buffered reader rather than read into memory whole.
+/// The same match copied into several subdirectories is only reported once, keeping
+/// the copy with the smallest path so repeated loads pick the same file.
+pub fn load_json_files_with_progress(
+    folder_path: &str,
+    follow_symlinks: bool,
+    progress_callback: impl Fn(usize, usize) + Sync
+) -> Result<LoadResult, ProcessorError> {
+    let options = LoadOptions {
+        follow_symlinks,
+        ..LoadOptions::default()
+    };
+    load_json_files_with_cache(folder_path, None, &options, progress_callback)
+}
+
+/// Most parse errors kept in a folder validation report
+const MAX_SAMPLE_ERRORS: usize = 5;
+
+/// Count how many match files in a folder actually parse, without loading them
+///
+/// Lets the UI warn about a wrong folder before a full load. A missing folder
+/// simply reports zero files.
+pub fn validate_vct_folder(folder_path: &str, follow_symlinks: bool) -> FolderValidation {
+    let files: Vec<PathBuf> = WalkDir::new(folder_path)
+        .follow_links(follow_symlinks)
         .into_iter()
         .filter_map(|e| e.ok())
-    {
-        let file_path = entry.path();
+        .filter(is_match_entry)
+        .map(|e| e.into_path())
+        .collect();
+
+    let results: Vec<Result<usize, ProcessorError>> = files
+        .par_iter()
+        .map(|path| read_summary_file(path, false).map(|summaries| summaries.len()))
+        .collect();
+
+    let mut validation = FolderValidation {
+        total_files: files.len(),
+        valid_vct_files: 0,
+        sample_errors: Vec::new(),
+    };
 
-        if file_path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                if let Ok(data) = serde_json::from_str::<VctMatchData>(&content) {
-                    index.insert(data.match_info.match_id.clone(), file_path.to_path_buf());
+    for (path, result) in files.iter().zip(results) {
+        match result {
+            Ok(count) if count > 0 => validation.valid_vct_files += 1,
+            Ok(_) => {}
+            Err(e) => {
+                if validation.sample_errors.len() < MAX_SAMPLE_ERRORS {
+                    validation.sample_errors.push(format!("Error loading {}: {}", path.display(), e));
                 }
             }
         }
     }
 
-    // Store index globally for fast lookups
-    unsafe {
-        MATCH_INDEX = Some(index);
+    validation
+}
+
+/// Build the summary and roster kept for a match during loading
+fn index_match_data(path: &Path, data: &VctMatchSummaryData) -> IndexedSummary {
+    IndexedSummary {
+        summary: parse_match_summary(path, data),
+        roster: data.players
+            .iter()
+            .map(|player| PlayerRef {
+                puuid: player.puuid.clone(),
+                game_name: player.game_name.clone(),
+                tag_line: player.tag_line.clone(),
+            })
+            .collect(),
     }
+}
 
-    Ok(matches)
+/// Whether a path looks like a match file the loaders understand
+pub fn is_match_file(path: &Path) -> bool {
+    is_gzip_json(path) || matches!(path.extension().and_then(|s| s.to_str()), Some("json" | "jsonl" | "ndjson"))
 }
 
-/// Get match detail by ID using index for fast lookup
-pub fn get_match_by_id(folder_path: &str, match_id: &str) -> Result<MatchDetail, String> {
-    // First try to use the index for fast lookup
-    unsafe {
-        if let Some(ref index) = MATCH_INDEX {
-            if let Some(file_path) = index.get(match_id) {
-                if let Ok(content) = fs::read_to_string(file_path) {
-                    if let Ok(data) = serde_json::from_str::<VctMatchData>(&content) {
-                        return Ok(parse_match_detail(file_path, &data));
-                    }
-                }
-            }
-        }
+/// Whether a walked entry is a match file, rather than a symlink the walk isn't following
+fn is_match_entry(entry: &walkdir::DirEntry) -> bool {
+    entry.file_type().is_file() && is_match_file(entry.path())
+}
+
+/// Whether a path is a gzip-compressed single-match `.json.gz` file
+fn is_gzip_json(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|name| name.ends_with(".json.gz"))
+}
+
+/// Read one match from a `.json` or `.json.gz` file, decompressing on the fly
+pub fn read_match_file<T: MatchData>(path: &Path) -> Result<T, ProcessorError> {
+    let file = File::open(path)?;
+
+    if is_gzip_json(path) {
+        read_match_data(GzDecoder::new(file))
+    } else {
+        read_match_data(file)
     }
+}
 
-    // Fallback to scanning if index lookup fails (shouldn't happen in normal operation)
-    let path = Path::new(folder_path);
+/// Top-level key that marks a file as a normalized export rather than raw VCT data
+#[derive(serde::Deserialize)]
+struct NormalizedProbe {
+    kill_events: Option<serde::de::IgnoredAny>,
+}
 
-    if !path.exists() {
-        return Err(format!("Folder does not exist: {}", folder_path));
+/// Whether a plain `.json` file is a match in the app's normalized schema
+fn is_normalized_json(path: &Path) -> bool {
+    if path.extension().and_then(|s| s.to_str()) != Some("json") {
+        return false;
     }
 
-    // Walk directory tree to find matching file
-    for entry in WalkDir::new(path)
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let file_path = entry.path();
+    File::open(path)
+        .ok()
+        .and_then(|file| serde_json::from_reader::<_, NormalizedProbe>(BufReader::new(file)).ok())
+        .is_some_and(|probe| probe.kill_events.is_some())
+}
 
-        if file_path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                if let Ok(data) = serde_json::from_str::<VctMatchData>(&content) {
-                    if data.match_info.match_id == match_id {
-                        return Ok(parse_match_detail(file_path, &data));
-                    }
-                }
-            }
+/// Read a match previously saved in the app's normalized schema
+///
+/// The detail is taken as written, so none of the VCT parsing runs again.
+pub fn load_normalized_json(path: &Path) -> Result<MatchDetail, ProcessorError> {
+    let file = File::open(path)?;
+    let detail: MatchDetail = serde_json::from_reader(BufReader::new(file))?;
+
+    if detail.match_id.is_empty() {
+        return Err(ProcessorError::InvalidData("match_id is empty".to_string()));
+    }
+    Ok(detail)
+}
+
+/// Build the summary and roster kept for a normalized match during loading
+///
+/// Normalized files don't record the queue or game version, so those stay unset.
+fn index_normalized_detail(detail: &MatchDetail) -> IndexedSummary {
+    let competing = || detail.players.iter().filter(|p| p.team_id == "Blue" || p.team_id == "Red");
+
+    let mut teams: Vec<String> = Vec::new();
+    let mut team_sizes: HashMap<String, u32> = HashMap::new();
+    for player in competing() {
+        if !teams.contains(&player.team_id) {
+            teams.push(player.team_id.clone());
         }
+        *team_sizes.entry(player.team_id.clone()).or_insert(0) += 1;
+    }
+
+    let wins = |team: &str| detail.rounds.iter().filter(|r| r.winning_team.as_deref() == Some(team)).count() as i32;
+    let (blue_wins, red_wins) = (wins("Blue"), wins("Red"));
+
+    IndexedSummary {
+        summary: MatchSummary {
+            match_id: detail.match_id.clone(),
+            map: detail.map.clone(),
+            map_display: detail.map_display.clone(),
+            region: detail.region.clone(),
+            game_start: detail.game_start,
+            game_start_valid: detail.game_start_valid,
+            is_valid_match: !teams.is_empty(),
+            teams,
+            team_sizes,
+            score: format!("{}-{}", blue_wins, red_wins),
+            rounds_counted: blue_wins + red_wins,
+            game_length_millis: detail.game_length_millis,
+            game_length_display: detail.game_length_millis.map(utils::format_game_duration),
+            queue: None,
+            game_version: None,
+        },
+        roster: detail.players
+            .iter()
+            .map(|player| PlayerRef {
+                puuid: player.puuid.clone(),
+                game_name: player.game_name.clone(),
+                tag_line: player.tag_line.clone(),
+            })
+            .collect(),
     }
+}
 
-    Err(format!("Match not found with ID: {}", match_id))
+/// Whether a path is a newline-delimited archive holding one match per line
+fn is_ndjson(path: &Path) -> bool {
+    matches!(path.extension().and_then(|s| s.to_str()), Some("jsonl" | "ndjson"))
 }
 
-/// Load multiple match details in controlled batches to prevent system overload
-pub fn get_multiple_match_details_batched(
-    folder_path: &str,
-    match_ids: &[String],
-    batch_size: usize,
-    progress_callback: impl Fn(usize, usize)
-) -> Result<Vec<MatchDetail>, String> {
-    let mut results = Vec::with_capacity(match_ids.len());
-    let total_matches = match_ids.len();
+/// Parse every match in a newline-delimited archive, skipping blank lines
+///
+/// A line that fails to parse is logged and skipped so one bad match doesn't
+/// hide the rest of the archive.
+fn read_ndjson_matches<D: MatchData, T>(
+    file_path: &Path,
+    mut convert: impl FnMut(D) -> Option<T>
+) -> Result<Vec<T>, ProcessorError> {
+    let file = File::open(file_path)?;
 
-    // Process in controlled batches to prevent system overload
-    for (batch_start, batch) in match_ids.chunks(batch_size).enumerate() {
-        let batch_index = batch_start * batch_size;
-
-        // Process batch in parallel
-        let handles: Vec<_> = batch.iter().enumerate().map(|(i, match_id)| {
-            let match_id = match_id.clone();
-            let folder_path = folder_path.to_string();
-            let global_index = batch_index + i;
-
-            std::thread::spawn(move || {
-                let result = get_match_by_id(&folder_path, &match_id);
-                (global_index, result)
-            })
-        }).collect();
-
-        // Collect results from this batch
-        let mut batch_results = vec![None; batch.len()];
-        for handle in handles {
-            match handle.join() {
-                Ok((index, result)) => {
-                    let batch_index = index - batch_start * batch_size;
-                    batch_results[batch_index] = Some(result);
-                },
-                Err(_) => return Err("Thread panicked while loading match details".to_string()),
-            }
+    let mut results = Vec::new();
+    for (line_index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
         }
 
-        // Process batch results
-        for result in batch_results {
-            match result {
-                Some(Ok(detail)) => {
-                    results.push(detail);
-                    // Report progress after each successful load
-                    progress_callback(results.len(), total_matches);
-                },
-                Some(Err(e)) => return Err(format!("Failed to load match detail: {}", e)),
-                None => return Err("Missing result from batch processing".to_string()),
-            }
+        match read_match_data(line.as_bytes()) {
+            Ok(data) => results.extend(convert(data)),
+            Err(e) => eprintln!("Error parsing {} line {}: {}", file_path.display(), line_index + 1, e),
         }
-
-        // Small delay between batches to prevent overwhelming the system
-        std::thread::sleep(std::time::Duration::from_millis(10));
     }
 
     Ok(results)
 }
 
-/// Load multiple match details in parallel for better performance (with default batching)
-pub fn get_multiple_match_details(folder_path: &str, match_ids: &[String]) -> Result<Vec<MatchDetail>, String> {
-    // Use batch size of 10 to balance speed vs system load
-    // Progress callback does nothing by default
-    get_multiple_match_details_batched(folder_path, match_ids, 10, |_, _| {})
+/// Read and parse a match file into summaries, using mmap when requested and compiled in
+///
+/// Plain `.json` and `.json.gz` files yield one summary; `.jsonl`/`.ndjson` archives yield one per line.
+/// Only the summary fields are deserialized, so kill data is skipped rather than parsed.
+fn read_summary_file(file_path: &Path, use_mmap: bool) -> Result<Vec<IndexedSummary>, ProcessorError> {
+    if is_ndjson(file_path) {
+        return read_ndjson_matches(file_path, |data| Some(index_match_data(file_path, &data)));
+    }
+
+    #[cfg(feature = "mmap")]
+    let data = if use_mmap && !is_gzip_json(file_path) {
+        read_json_mmap(file_path)
+    } else {
+        read_match_file(file_path)
+    };
+    #[cfg(not(feature = "mmap"))]
+    let data = {
+        let _ = use_mmap;
+        read_match_file(file_path)
+    };
+
+    match data {
+        Ok(data) => Ok(vec![index_match_data(file_path, &data)]),
+        // Only probe for a normalized export once the VCT parse has failed, so raw files aren't read twice
+        Err(_) if is_normalized_json(file_path) => load_normalized_json(file_path)
+            .map(|detail| vec![index_normalized_detail(&detail)]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read one match from a file, looking through every line of an archive
+///
+/// Raw VCT data goes through `convert`; normalized exports, which keep no raw
+/// rounds, go through `from_normalized`.
+fn read_match_from_file<T>(
+    file_path: &Path,
+    match_id: &str,
+    convert: impl FnOnce(VctMatchData) -> T,
+    from_normalized: impl FnOnce(MatchDetail) -> T
+) -> Option<T> {
+    if let Some((zip_path, entry_name)) = split_zip_entry_path(file_path) {
+        let data: VctMatchData = read_zip_entry(&zip_path, &entry_name).ok()?;
+        return (data.match_info.match_id == match_id).then(|| convert(data));
+    }
+
+    if is_ndjson(file_path) {
+        return read_ndjson_matches(file_path, |data: VctMatchData| {
+            (data.match_info.match_id == match_id).then_some(data)
+        })
+        .ok()?
+        .into_iter()
+        .next()
+        .map(convert);
+    }
+
+    match read_match_file::<VctMatchData>(file_path) {
+        Ok(data) => (data.match_info.match_id == match_id).then(|| convert(data)),
+        Err(_) if is_normalized_json(file_path) => {
+            load_normalized_json(file_path).ok().filter(|detail| detail.match_id == match_id).map(from_normalized)
+        }
+        Err(_) => None,
+    }
+}
+
+/// Files read by [`read_detail_from_file`], so tests can tell cache hits from disk reads
+#[cfg(test)]
+static DETAIL_READS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Parse the detail for one match from a file
+fn read_detail_from_file(file_path: &Path, match_id: &str) -> Option<MatchDetail> {
+    #[cfg(test)]
+    DETAIL_READS.fetch_add(1, Ordering::SeqCst);
+
+    read_match_from_file(file_path, match_id, |data| parse_match_detail(file_path, &data), |detail| detail)
+}
+
+/// Read the raw rounds for one match from a file
+///
+/// Normalized exports keep no raw rounds, so finding the match in one is an error
+/// rather than a match with no rounds.
+fn read_rounds_from_file(file_path: &Path, match_id: &str) -> Option<Result<MatchRounds, ProcessorError>> {
+    read_match_from_file(
+        file_path,
+        match_id,
+        |data| Ok(MatchRounds {
+            map_display: normalize_map_name(&data.match_info.map),
+            round_results: data.round_results,
+        }),
+        |detail| Err(ProcessorError::InvalidData(format!(
+            "match {} is a normalized export, which has no round data",
+            detail.match_id
+        ))),
+    )
+}
+
+/// Path used to index a JSON entry inside a zip archive, as if the archive were a folder
+fn zip_entry_path(zip_path: &Path, entry_name: &str) -> PathBuf {
+    zip_path.join(entry_name)
+}
+
+/// Split an indexed zip entry path back into the archive and the entry name
+fn split_zip_entry_path(path: &Path) -> Option<(PathBuf, String)> {
+    let zip_path = path.ancestors().skip(1).find(|ancestor| {
+        ancestor.extension().and_then(|s| s.to_str()) == Some("zip") && ancestor.is_file()
+    })?;
+
+    // Zip entry names always use forward slashes
+    let entry_name = path.strip_prefix(zip_path).ok()?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    Some((zip_path.to_path_buf(), entry_name))
+}
+
+/// Keep I/O failures inside an archive as read errors; anything else means a broken archive
+fn zip_error(zip_path: &Path, e: zip::result::ZipError) -> ProcessorError {
+    match e {
+        zip::result::ZipError::Io(e) => ProcessorError::FileRead(e),
+        e => ProcessorError::InvalidData(format!("Invalid zip archive {}: {}", zip_path.display(), e)),
+    }
+}
+
+fn open_zip(zip_path: &Path) -> Result<zip::ZipArchive<File>, ProcessorError> {
+    let file = File::open(zip_path)?;
+    zip::ZipArchive::new(file).map_err(|e| zip_error(zip_path, e))
+}
+
+/// Read one match entry out of a zip archive
+fn read_zip_entry<T: MatchData>(zip_path: &Path, entry_name: &str) -> Result<T, ProcessorError> {
+    let mut archive = open_zip(zip_path)?;
+    let entry = archive.by_name(entry_name).map_err(|e| zip_error(zip_path, e))?;
+    read_match_data(entry)
+}
+
+/// Parse every `.json` entry in a zip archive, in archive order
+///
+/// Entries are read into memory one at a time; ones that fail to parse are logged and skipped.
+fn read_zip_summaries(zip_path: &Path) -> Result<Vec<(PathBuf, IndexedSummary)>, ProcessorError> {
+    let mut archive = open_zip(zip_path)?;
+    let mut summaries = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| zip_error(zip_path, e))?;
+
+        if !entry.is_file() || !entry.name().ends_with(".json") {
+            continue;
+        }
+
+        let entry_path = zip_entry_path(zip_path, entry.name());
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        if let Err(e) = entry.read_to_end(&mut contents) {
+            eprintln!("Error reading {}: {}", entry_path.display(), e);
+            continue;
+        }
+
+        match read_match_data(contents.as_slice()) {
+            Ok(data) => summaries.push((entry_path.clone(), index_match_data(&entry_path, &data))),
+            Err(e) => eprintln!("Error parsing {}: {}", entry_path.display(), e),
+        }
+    }
+
+    Ok(summaries)
+}
+
+/// Memory-map a match file and deserialize it without copying it into a String
+#[cfg(feature = "mmap")]
+pub fn read_json_mmap<T: MatchData>(path: &Path) -> Result<T, ProcessorError> {
+    let file = File::open(path)?;
+
+    // Safety: the mapping is read-only and dropped before returning; a file truncated
+    // underneath us surfaces as a parse error rather than being relied upon afterwards
+    let mmap = unsafe { memmap2::Mmap::map(&file) }?;
+
+    let data: T = serde_json::from_slice(&mmap)?;
+    validate_match_data(&data).map_err(|errors| ProcessorError::InvalidData(errors.join("; ")))?;
+
+    Ok(data)
+}
+
+/// File modification time in milliseconds since the epoch
+fn modified_millis(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(since_epoch.as_millis() as i64)
+}
+
+/// Load all JSON files from a directory, reusing summaries from the SQLite cache when given
+///
+/// Files whose path and modification time match a cached row are not reparsed;
+/// new or changed files are parsed and written back to the cache.
+pub fn load_json_files_with_cache(
+    folder_path: &str,
+    db: Option<&mut SummaryDb>,
+    options: &LoadOptions,
+    progress_callback: impl Fn(usize, usize) + Sync
+) -> Result<LoadResult, ProcessorError> {
+    load_folder(folder_path, db, options, progress_callback).map(|loaded| loaded.result)
+}
+
+/// Game version prefixes whose match schema the parser is known to handle
+///
+/// Matches from other versions still load, but are flagged in [`LoadResult::warnings`].
+const SUPPORTED_GAME_VERSIONS: &[&str] = &["release-07.", "release-08.", "release-09.", "release-10.", "release-11."];
+
+/// Whether a match's game version is one the parser is known to handle
+pub fn is_supported_game_version(version: &str) -> bool {
+    SUPPORTED_GAME_VERSIONS.iter().any(|prefix| version.starts_with(prefix))
+}
+
+/// Load a folder, also returning its match index and the roster of every kept match
+fn load_folder(
+    folder_path: &str,
+    db: Option<&mut SummaryDb>,
+    options: &LoadOptions,
+    progress_callback: impl Fn(usize, usize) + Sync
+) -> Result<FolderIndexCache, ProcessorError> {
+    let path = Path::new(folder_path);
+
+    if !path.exists() {
+        return Err(ProcessorError::FolderNotFound(folder_path.to_string()));
+    }
+
+    let mut all_files = Vec::new();
+
+    // Collect all JSON file paths first. When following links, WalkDir reports
+    // symlink loops as errors, which are skipped like any other unreadable entry
+    for entry in WalkDir::new(path)
+        .follow_links(options.follow_symlinks)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if is_match_entry(&entry) {
+            all_files.push(entry.into_path());
+        }
+    }
+
+    // Sort so duplicates resolve to the smallest path regardless of walk order
+    all_files.sort();
+
+    let cached = match db.as_deref() {
+        Some(db) => db.load_all().unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    };
+
+    let total_files = all_files.len();
+    let processed = Mutex::new(0usize);
+    let fresh = Mutex::new(Vec::new());
+
+    // Parse files (in parallel by default); collect keeps the sorted order so deduplication stays deterministic
+    let load_one = |file_path: &PathBuf| {
+        let mtime = modified_millis(file_path);
+
+        let result = match cached.get(file_path) {
+            Some((cached_mtime, summary)) if Some(*cached_mtime) == mtime => Ok(summary.clone()),
+            _ => read_summary_file(file_path, options.use_mmap).inspect(|summary| {
+                if let Some(mtime) = mtime {
+                    fresh.lock().unwrap().push((file_path.clone(), mtime, summary.clone()));
+                }
+            }),
+        };
+
+        let mut processed = processed.lock().unwrap();
+        *processed += 1;
+
+        // Report progress every 10 files or at key milestones
+        if *processed % 10 == 0 || *processed == total_files || *processed == 1 {
+            progress_callback(*processed, total_files);
+        }
+
+        result
+    };
+
+    let parsed: Vec<Result<Vec<IndexedSummary>, ProcessorError>> = if options.parallel {
+        all_files
+            .par_iter()
+            .with_min_len(options.batch_size.max(1))
+            .map(&load_one)
+            .collect()
+    } else {
+        all_files.iter().map(&load_one).collect()
+    };
+
+    let mut matches = Vec::new();
+    let mut rosters = HashMap::new();
+    let mut index = HashMap::new();
+    let mut seen_ids = std::collections::HashSet::new();
+    let mut duplicates_skipped = 0;
+    let mut parse_errors = 0;
+    let mut warnings = Vec::new();
+
+    for (file_path, result) in all_files.iter().zip(parsed) {
+        match result {
+            Ok(summaries) => {
+                for IndexedSummary { summary, roster } in summaries {
+                    if seen_ids.insert(summary.match_id.clone()) {
+                        index.insert(summary.match_id.clone(), index_entry(path, file_path));
+                        if let Some(version) = summary.game_version.as_deref().filter(|v| !is_supported_game_version(v)) {
+                            let warning = format!("Match {} has unsupported game version {}", summary.match_id, version);
+                            eprintln!("{}", warning);
+                            warnings.push(warning);
+                        }
+                        rosters.insert(summary.match_id.clone(), roster);
+                        matches.push(summary);
+                    } else {
+                        duplicates_skipped += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                // Continue processing other files even if one fails
+                eprintln!("Error loading {}: {}", file_path.display(), e);
+                parse_errors += 1;
+            }
+        }
+    }
+
+    if let Some(db) = db {
+        if let Err(e) = db.upsert_all(&fresh.into_inner().unwrap()) {
+            eprintln!("{}", e);
+        }
+    }
+
+    let result = LoadResult {
+        no_matches_found: matches.is_empty(),
+        summaries: matches,
+        duplicates_skipped,
+        parse_errors,
+        warnings,
+    };
+
+    Ok(FolderIndexCache {
+        file_count: total_files,
+        index,
+        result,
+        rosters,
+    })
+}
+
+/// Index of match IDs to file paths for fast lookup
+static MATCH_INDEX: Mutex<Option<HashMap<String, IndexEntry>>> = Mutex::new(None);
+
+/// Summaries of every match currently loaded, kept in sync with the index
+static LOADED_MATCHES: Mutex<Vec<MatchSummary>> = Mutex::new(Vec::new());
+
+/// [`LOADED_MATCHES`] in the default sort order, built on the first page request after a change
+static SORTED_MATCHES: Mutex<Option<Vec<MatchSummary>>> = Mutex::new(None);
+
+/// Drop the sorted page list so the next page request rebuilds it from [`LOADED_MATCHES`]
+fn clear_sorted_matches() {
+    *SORTED_MATCHES.lock().unwrap() = None;
+}
+
+/// Root folders the current index was built from
+static LOADED_ROOTS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Index entries found pointing at missing files since the last load
+static STALE_INDEX_ENTRIES: Mutex<Vec<StaleIndexEntry>> = Mutex::new(Vec::new());
+
+/// Called with every stale entry so far whenever a new one is found
+type StaleIndexListener = Box<dyn Fn(&[StaleIndexEntry]) + Send>;
+static STALE_INDEX_LISTENER: Mutex<Option<StaleIndexListener>> = Mutex::new(None);
+
+/// Every player seen in the loaded matches, with a name lookup for search
+static PLAYER_INDEX: Mutex<Option<PlayerIndex>> = Mutex::new(None);
+
+/// Longest key stored in the player name index
+const NAME_KEY_LEN: usize = 3;
+
+#[derive(Default)]
+struct PlayerIndex {
+    /// Keyed by PUUID
+    players: HashMap<String, PlayerIndexEntry>,
+    /// Lowercased prefix of up to [`NAME_KEY_LEN`] characters → PUUIDs
+    ///
+    /// Every suffix of `name#tag` is indexed, so any substring of a name can be
+    /// looked up by its first characters. Entries are only ever added, so a
+    /// player who was renamed or dropped can still be listed here.
+    name_prefixes: HashMap<String, Vec<String>>,
+}
+
+/// Latest known name for a player and the matches they appear in
+struct PlayerIndexEntry {
+    game_name: String,
+    tag_line: String,
+    match_ids: Vec<String>,
+}
+
+/// Lowercased keys a name is indexed under: the first characters of each suffix of `name#tag`
+fn name_index_keys(game_name: &str, tag_line: &str) -> Vec<String> {
+    let full: Vec<char> = format!("{}#{}", game_name, tag_line).to_lowercase().chars().collect();
+    let mut keys: Vec<String> = (0..full.len())
+        .flat_map(|start| (start + 1..=full.len().min(start + NAME_KEY_LEN)).map(move |end| (start, end)))
+        .map(|(start, end)| full[start..end].iter().collect())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn add_to_player_index(index: &mut PlayerIndex, match_id: &str, roster: Vec<PlayerRef>) {
+    for player in roster {
+        let entry = index.players.entry(player.puuid.clone()).or_insert_with(|| PlayerIndexEntry {
+            game_name: String::new(),
+            tag_line: String::new(),
+            match_ids: Vec::new(),
+        });
+
+        // Only new players and renames need indexing, which keeps the key lists free of repeats
+        if entry.game_name != player.game_name || entry.tag_line != player.tag_line || entry.match_ids.is_empty() {
+            for key in name_index_keys(&player.game_name, &player.tag_line) {
+                index.name_prefixes.entry(key).or_default().push(player.puuid.clone());
+            }
+        }
+
+        entry.game_name = player.game_name;
+        entry.tag_line = player.tag_line;
+        entry.match_ids.push(match_id.to_string());
+    }
+}
+
+/// Index entry for a file found under `root`
+fn index_entry(root: &Path, file_path: &Path) -> IndexEntry {
+    IndexEntry {
+        path: file_path.to_path_buf(),
+        relative_path: file_path.strip_prefix(root).unwrap_or(file_path).to_path_buf(),
+    }
+}
+
+/// Register a callback for index entries whose files have moved, replacing any previous one
+pub fn set_stale_index_listener(listener: impl Fn(&[StaleIndexEntry]) + Send + 'static) {
+    *STALE_INDEX_LISTENER.lock().unwrap() = Some(Box::new(listener));
+}
+
+/// Record an index entry that no longer points at its file and notify the listener
+fn report_stale_entry(entry: StaleIndexEntry) {
+    let mut stale = STALE_INDEX_ENTRIES.lock().unwrap();
+    stale.retain(|e| e.match_id != entry.match_id);
+    stale.push(entry);
+
+    if let Some(listener) = STALE_INDEX_LISTENER.lock().unwrap().as_ref() {
+        listener(&stale);
+    }
+}
+
+/// Forget stale entries once the index has been rebuilt
+fn clear_stale_entries() {
+    STALE_INDEX_ENTRIES.lock().unwrap().clear();
+}
+
+/// 64-bit FNV-1a hash, which unlike `DefaultHasher` is fixed across Rust releases
+fn stable_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Build the on-disk cache location for a folder's match index
+pub fn index_cache_path(data_dir: &Path, folder_path: &str) -> PathBuf {
+    // One cache file per root folder so switching folders doesn't invalidate the other
+    data_dir.join(format!("match_index_{:016x}.bin", stable_hash(folder_path)))
+}
+
+/// Serialize a folder's index and summaries to disk
+pub fn save_index(cache: &FolderIndexCache, cache_path: &Path) -> Result<(), ProcessorError> {
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let bytes = bincode::serialize(cache)
+        .map_err(|e| ProcessorError::Other(format!("Failed to serialize match index: {}", e)))?;
+
+    Ok(fs::write(cache_path, bytes)?)
+}
+
+/// Deserialize a previously saved folder index from disk
+pub fn load_index(cache_path: &Path) -> Result<FolderIndexCache, ProcessorError> {
+    let bytes = fs::read(cache_path)?;
+
+    bincode::deserialize(&bytes)
+        .map_err(|e| ProcessorError::InvalidData(format!("Corrupt match index {}: {}", cache_path.display(), e)))
+}
+
+/// Check whether a cached index still matches the folder: no files added or
+/// removed, and none modified since the cache was written
+fn is_index_fresh(cache_path: &Path, folder: &Path, follow_symlinks: bool, file_count: usize) -> bool {
+    let cache_modified = match fs::metadata(cache_path).and_then(|m| m.modified()) {
+        Ok(time) => time,
+        Err(_) => return false,
+    };
+
+    // Only metadata is read here, so this is far cheaper than reparsing every file
+    let mut files_seen = 0;
+    let all_older = WalkDir::new(folder)
+        .follow_links(follow_symlinks)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(is_match_entry)
+        .inspect(|_| files_seen += 1)
+        .all(|e| e.metadata().ok().and_then(|m| m.modified().ok()).is_some_and(|modified| modified <= cache_modified));
+
+    all_older && files_seen == file_count
+}
+
+/// Restore a folder from its index cache if it is still valid, otherwise load it and persist the cache
+///
+/// A valid cache skips parsing entirely, so progress jumps straight to done.
+fn load_folder_cached(
+    folder_path: &str,
+    cache_path: Option<&Path>,
+    db: Option<&mut SummaryDb>,
+    options: &LoadOptions,
+    progress_callback: impl Fn(usize, usize) + Sync
+) -> Result<FolderIndexCache, ProcessorError> {
+    if let Some(cache_path) = cache_path {
+        match load_index(cache_path) {
+            Ok(cache) if is_index_fresh(cache_path, Path::new(folder_path), options.follow_symlinks, cache.file_count) => {
+                progress_callback(cache.file_count, cache.file_count);
+                return Ok(cache);
+            }
+            Ok(_) => {}
+            Err(ProcessorError::FileRead(_)) => {}
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    let loaded = load_folder(folder_path, db, options, progress_callback)?;
+
+    if let Some(cache_path) = cache_path {
+        if let Err(e) = save_index(&loaded, cache_path) {
+            eprintln!("{}", e);
+        }
+    }
+
+    Ok(loaded)
+}
+
+/// Load all JSON files from a directory and build index
+///
+/// When `cache_dir` is given the index and summaries are restored from disk without
+/// parsing, unless the cache is missing, corrupt, or files were added, removed or modified.
+pub fn load_json_files(folder_path: &str, cache_dir: Option<&Path>, options: &LoadOptions) -> Result<LoadResult, ProcessorError> {
+    load_json_files_multi(&[folder_path.to_string()], cache_dir, options)
+}
+
+/// Load all JSON files from several root folders into a single index
+///
+/// Matches present under more than one root are kept from the first root listed.
+pub fn load_json_files_multi(folder_paths: &[String], cache_dir: Option<&Path>, options: &LoadOptions) -> Result<LoadResult, ProcessorError> {
+    load_json_files_multi_with_progress(folder_paths, cache_dir, options, |_, _| {})
+}
+
+/// Same as [`load_json_files_multi`], reporting `(processed, total)` files as each folder loads
+///
+/// Counts restart at zero for every folder.
+pub fn load_json_files_multi_with_progress(
+    folder_paths: &[String],
+    cache_dir: Option<&Path>,
+    options: &LoadOptions,
+    progress_callback: impl Fn(usize, usize) + Sync
+) -> Result<LoadResult, ProcessorError> {
+    let mut merged = LoadResult {
+        summaries: Vec::new(),
+        duplicates_skipped: 0,
+        parse_errors: 0,
+        warnings: Vec::new(),
+        no_matches_found: false,
+    };
+    let mut merged_index = HashMap::new();
+    let mut player_index = PlayerIndex::default();
+    let mut seen_ids = std::collections::HashSet::new();
+
+    // The summary cache is best-effort; loading still works if it can't be opened
+    let mut db = cache_dir.and_then(|dir| {
+        SummaryDb::open(&summary_db_path(dir))
+            .map_err(|e| eprintln!("{}", e))
+            .ok()
+    });
+
+    for folder_path in folder_paths {
+        let cache_path = cache_dir.map(|dir| index_cache_path(dir, folder_path));
+        let FolderIndexCache { index, result, mut rosters, .. } =
+            load_folder_cached(folder_path, cache_path.as_deref(), db.as_mut(), options, &progress_callback)?;
+
+        merged.duplicates_skipped += result.duplicates_skipped;
+        merged.parse_errors += result.parse_errors;
+        merged.warnings.extend(result.warnings);
+
+        for summary in result.summaries {
+            if seen_ids.insert(summary.match_id.clone()) {
+                if let Some(roster) = rosters.remove(&summary.match_id) {
+                    add_to_player_index(&mut player_index, &summary.match_id, roster);
+                }
+                merged.summaries.push(summary);
+            } else {
+                merged.duplicates_skipped += 1;
+            }
+        }
+
+        for (match_id, entry) in index {
+            merged_index.entry(match_id).or_insert(entry);
+        }
+    }
+
+    merged.no_matches_found = merged.summaries.is_empty();
+
+    // Store index globally for fast lookups
+    *MATCH_INDEX.lock().unwrap() = Some(merged_index);
+    *LOADED_MATCHES.lock().unwrap() = merged.summaries.clone();
+    *LOADED_ROOTS.lock().unwrap() = folder_paths.iter().map(PathBuf::from).collect();
+    *PLAYER_INDEX.lock().unwrap() = Some(player_index);
+    clear_sorted_matches();
+
+    // A reload may point at different files, so cached details can't be trusted
+    clear_match_cache();
+    clear_stale_entries();
+
+    Ok(merged)
+}
+
+/// Location of the SQLite summary cache inside the app data directory
+pub fn summary_db_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("match_summaries.sqlite")
+}
+
+/// Drop every cached summary and index for the loaded folders, then reload them from scratch
+pub fn rebuild_index(cache_dir: Option<&Path>, options: &LoadOptions) -> Result<LoadResult, ProcessorError> {
+    let folder_paths: Vec<String> = LOADED_ROOTS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|root| root.to_string_lossy().into_owned())
+        .collect();
+
+    if folder_paths.is_empty() {
+        return Err(ProcessorError::Other("No folder has been loaded yet".to_string()));
+    }
+
+    if let Some(dir) = cache_dir {
+        SummaryDb::open(&summary_db_path(dir))?.clear()?;
+
+        for folder_path in &folder_paths {
+            let cache_path = index_cache_path(dir, folder_path);
+            if cache_path.exists() {
+                fs::remove_file(&cache_path)?;
+            }
+        }
+    }
+
+    load_json_files_multi(&folder_paths, cache_dir, options)
+}
+
+/// Load every match in a zip archive as the current session, replacing any loaded folders
+///
+/// Matches repeated inside the archive are only kept once. Details are read back
+/// out of the archive on demand, so it must stay in place while in use.
+pub fn load_from_zip(zip_path: &Path) -> Result<Vec<MatchSummary>, ProcessorError> {
+    if !zip_path.is_file() {
+        return Err(ProcessorError::FolderNotFound(zip_path.display().to_string()));
+    }
+
+    let mut summaries = Vec::new();
+    let mut index = HashMap::new();
+    let mut player_index = PlayerIndex::default();
+
+    for (entry_path, IndexedSummary { summary, roster }) in read_zip_summaries(zip_path)? {
+        if index.contains_key(&summary.match_id) {
+            continue;
+        }
+
+        add_to_player_index(&mut player_index, &summary.match_id, roster);
+        index.insert(summary.match_id.clone(), index_entry(zip_path, &entry_path));
+        summaries.push(summary);
+    }
+
+    *MATCH_INDEX.lock().unwrap() = Some(index);
+    *LOADED_MATCHES.lock().unwrap() = summaries.clone();
+    *LOADED_ROOTS.lock().unwrap() = vec![zip_path.to_path_buf()];
+    *PLAYER_INDEX.lock().unwrap() = Some(player_index);
+    clear_sorted_matches();
+    clear_match_cache();
+    clear_stale_entries();
+
+    Ok(summaries)
+}
+
+/// Parse a newly discovered match file and add its matches to the loaded matches and index
+///
+/// Returns only the matches that weren't already known; archives may add several.
+pub fn index_new_file(file_path: &Path) -> Result<Vec<MatchSummary>, ProcessorError> {
+    let root = LOADED_ROOTS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|root| file_path.starts_with(root))
+        .cloned()
+        .unwrap_or_default();
+    let mut added = Vec::new();
+    let mut loaded = LOADED_MATCHES.lock().unwrap();
+
+    for IndexedSummary { summary, roster } in read_summary_file(file_path, false)? {
+        if loaded.iter().any(|m| m.match_id == summary.match_id) {
+            continue;
+        }
+        loaded.push(summary.clone());
+        clear_sorted_matches();
+
+        add_to_player_index(
+            PLAYER_INDEX.lock().unwrap().get_or_insert_with(PlayerIndex::default),
+            &summary.match_id,
+            roster,
+        );
+
+        MATCH_INDEX
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(summary.match_id.clone(), index_entry(&root, file_path));
+
+        added.push(summary);
+    }
+
+    Ok(added)
+}
+
+/// Drop a match from the player index and detail cache
+fn forget_match_players(match_id: &str) {
+    if let Some(index) = PLAYER_INDEX.lock().unwrap().as_mut() {
+        for entry in index.players.values_mut() {
+            entry.match_ids.retain(|id| id != match_id);
+        }
+        index.players.retain(|_, entry| !entry.match_ids.is_empty());
+    }
+    if let Some(cache) = MATCH_CACHE.lock().unwrap().as_mut() {
+        cache.pop(match_id);
+    }
+}
+
+/// Forget a match that no longer exists on disk
+fn remove_loaded_match(match_id: &str) {
+    if let Some(index) = MATCH_INDEX.lock().unwrap().as_mut() {
+        index.remove(match_id);
+    }
+    LOADED_MATCHES.lock().unwrap().retain(|m| m.match_id != match_id);
+    clear_sorted_matches();
+    forget_match_players(match_id);
+}
+
+/// Reread a single match from its indexed file, replacing its summary and cached detail
+///
+/// If the file is gone, or no longer holds the match, the match is dropped from
+/// the index and an error is returned. A file that fails to parse is left indexed.
+pub fn refresh_match(folder_path: &str, match_id: &str) -> Result<MatchSummary, ProcessorError> {
+    ensure_folder_loaded(folder_path)?;
+
+    let path = MATCH_INDEX
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|index| index.get(match_id).map(|entry| entry.path.clone()))
+        .ok_or_else(|| ProcessorError::MatchNotFound(match_id.to_string()))?;
+
+    let file_path = split_zip_entry_path(&path).map_or_else(|| path.clone(), |(zip_path, _)| zip_path);
+    if !file_path.is_file() {
+        remove_loaded_match(match_id);
+        return Err(ProcessorError::FileRead(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} no longer exists", file_path.display()),
+        )));
+    }
+
+    let summaries = match split_zip_entry_path(&path) {
+        Some((zip_path, entry_name)) => {
+            let data: VctMatchSummaryData = read_zip_entry(&zip_path, &entry_name)?;
+            vec![index_match_data(&path, &data)]
+        }
+        None => read_summary_file(&path, false)?,
+    };
+
+    let Some(IndexedSummary { summary, roster }) = summaries
+        .into_iter()
+        .find(|indexed| indexed.summary.match_id == match_id)
+    else {
+        remove_loaded_match(match_id);
+        return Err(ProcessorError::MatchNotFound(match_id.to_string()));
+    };
+
+    let mut loaded = LOADED_MATCHES.lock().unwrap();
+    match loaded.iter_mut().find(|m| m.match_id == match_id) {
+        Some(existing) => *existing = summary.clone(),
+        None => loaded.push(summary.clone()),
+    }
+    clear_sorted_matches();
+
+    // Rebuild the roster rather than appending so swapped-out players don't linger
+    forget_match_players(match_id);
+    add_to_player_index(PLAYER_INDEX.lock().unwrap().get_or_insert_with(PlayerIndex::default), match_id, roster);
+
+    Ok(summary)
+}
+
+/// Default number of parsed match details kept in memory
+pub const DEFAULT_MATCH_CACHE_CAPACITY: usize = 64;
+
+/// Recently opened match details, keyed by match ID
+static MATCH_CACHE: Mutex<Option<LruCache<String, MatchDetail>>> = Mutex::new(None);
+
+fn new_match_cache(capacity: usize) -> LruCache<String, MatchDetail> {
+    LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN))
+}
+
+/// Change how many parsed match details are kept in memory
+pub fn set_match_cache_capacity(capacity: usize) {
+    let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+    MATCH_CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| new_match_cache(DEFAULT_MATCH_CACHE_CAPACITY))
+        .resize(capacity);
+}
+
+/// Drop all cached match details so the next lookup rereads from disk
+pub fn clear_match_cache() {
+    if let Some(cache) = MATCH_CACHE.lock().unwrap().as_mut() {
+        cache.clear();
+    }
+}
+
+/// Get match detail by ID, serving repeated opens from the in-memory cache
+///
+/// Without a `folder_path` the lookup relies on the index built by the last load.
+pub fn get_match_by_id(folder_path: Option<&str>, match_id: &str) -> Result<MatchDetail, ProcessorError> {
+    if let Some(detail) = MATCH_CACHE
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|cache| cache.get(match_id).cloned())
+    {
+        return Ok(detail);
+    }
+
+    let detail = read_match_by_id(folder_path, match_id, read_detail_from_file)?;
+
+    MATCH_CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| new_match_cache(DEFAULT_MATCH_CACHE_CAPACITY))
+        .put(match_id.to_string(), detail.clone());
+
+    Ok(detail)
+}
+
+/// Raw rounds of a match, for views built from every player's position at each kill
+///
+/// Unlike [`get_match_by_id`] this always reads the file, since raw rounds aren't cached.
+pub fn get_match_rounds(folder_path: Option<&str>, match_id: &str) -> Result<MatchRounds, ProcessorError> {
+    read_match_by_id(folder_path, match_id, read_rounds_from_file)?
+}
+
+/// Read a match from disk using index for fast lookup
+fn read_match_by_id<T>(
+    folder_path: Option<&str>,
+    match_id: &str,
+    read: impl Fn(&Path, &str) -> Option<T>
+) -> Result<T, ProcessorError> {
+    // First try to use the index for fast lookup
+    let (indexed, index_empty) = match MATCH_INDEX.lock().unwrap().as_ref() {
+        Some(index) => (index.get(match_id).cloned(), index.is_empty()),
+        None => (None, false),
+    };
+
+    // An empty index for the loaded folders means they held no matches, so a rescan can't find one
+    let folder_loaded = folder_path.is_none_or(|folder_path| {
+        LOADED_ROOTS
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|root| root == Path::new(folder_path))
+    });
+    if index_empty && folder_loaded {
+        return Err(ProcessorError::MatchNotFound(match_id.to_string()));
+    }
+
+    if let Some(entry) = indexed {
+        if let Some(detail) = read(&entry.path, match_id) {
+            return Ok(detail);
+        }
+
+        // The file may have moved along with its folder, so look for it at the same
+        // relative path under the folder the caller now points at
+        let relocated = folder_path
+            .map(|folder_path| Path::new(folder_path).join(&entry.relative_path))
+            .filter(|candidate| *candidate != entry.path)
+            .and_then(|candidate| read(&candidate, match_id).map(|detail| (candidate, detail)));
+
+        report_stale_entry(StaleIndexEntry {
+            match_id: match_id.to_string(),
+            indexed_path: entry.path.clone(),
+            relocated_path: relocated.as_ref().map(|(candidate, _)| candidate.clone()),
+        });
+
+        if let Some((candidate, detail)) = relocated {
+            if let Some(indexed) = MATCH_INDEX.lock().unwrap().as_mut().and_then(|index| index.get_mut(match_id)) {
+                indexed.path = candidate;
+            }
+            return Ok(detail);
+        }
+    }
+
+    // Fallback to scanning if index lookup fails (shouldn't happen in normal operation)
+    let roots = match folder_path {
+        Some(folder_path) => {
+            let path = PathBuf::from(folder_path);
+            if !path.exists() {
+                return Err(ProcessorError::FolderNotFound(folder_path.to_string()));
+            }
+            vec![path]
+        }
+        None => LOADED_ROOTS.lock().unwrap().clone(),
+    };
+
+    for root in &roots {
+        if let Some(detail) = scan_for_match(root, match_id, &read) {
+            return Ok(detail);
+        }
+    }
+
+    Err(ProcessorError::MatchNotFound(match_id.to_string()))
+}
+
+/// Walk directory tree to find the file for a match ID
+fn scan_for_match<T>(path: &Path, match_id: &str, read: impl Fn(&Path, &str) -> Option<T>) -> Option<T> {
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if is_match_entry(&entry) {
+            if let Some(found) = read(entry.path(), match_id) {
+                return Some(found);
+            }
+        }
+    }
+
+    None
+}
+
+/// Largest match file [`get_raw_match_json`] will return
+pub const MAX_RAW_JSON_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Read text from a reader, failing if it is larger than [`MAX_RAW_JSON_BYTES`]
+fn read_capped(reader: impl Read, path: &Path) -> Result<String, ProcessorError> {
+    let mut raw = String::new();
+    reader.take(MAX_RAW_JSON_BYTES + 1).read_to_string(&mut raw)?;
+
+    if raw.len() as u64 > MAX_RAW_JSON_BYTES {
+        return Err(ProcessorError::Other(format!(
+            "{} is larger than the {} MiB limit",
+            path.display(),
+            MAX_RAW_JSON_BYTES / (1024 * 1024)
+        )));
+    }
+    Ok(raw)
+}
+
+/// The source JSON behind a loaded match, for debugging the parser
+///
+/// `.json.gz` files are decompressed, and for zip archives and `.jsonl`/`.ndjson`
+/// files only the match's own entry or line is returned.
+pub fn get_raw_match_json(folder_path: &str, match_id: &str) -> Result<String, ProcessorError> {
+    ensure_folder_loaded(folder_path)?;
+
+    let path = MATCH_INDEX
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|index| index.get(match_id).map(|entry| entry.path.clone()))
+        .ok_or_else(|| ProcessorError::MatchNotFound(match_id.to_string()))?;
+
+    if let Some((zip_path, entry_name)) = split_zip_entry_path(&path) {
+        let mut archive = open_zip(&zip_path)?;
+        let entry = archive.by_name(&entry_name).map_err(|e| zip_error(&zip_path, e))?;
+        return read_capped(entry, &path);
+    }
+
+    let file = File::open(&path)?;
+
+    if is_ndjson(&path) {
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let is_match = serde_json::from_str::<VctMatchSummaryData>(&line)
+                .is_ok_and(|data| data.match_info.match_id == match_id);
+            if is_match {
+                return read_capped(line.as_bytes(), &path);
+            }
+        }
+        return Err(ProcessorError::MatchNotFound(match_id.to_string()));
+    }
+
+    if is_gzip_json(&path) {
+        read_capped(GzDecoder::new(file), &path)
+    } else {
+        read_capped(file, &path)
+    }
+}
+
+/// Load multiple match details in controlled batches to prevent system overload
+pub fn get_multiple_match_details_batched(
+    folder_path: Option<&str>,
+    match_ids: &[String],
+    batch_size: usize,
+    delay_millis: u64,
+    progress_callback: impl Fn(usize, usize)
+) -> Result<Vec<MatchDetail>, ProcessorError> {
+    let mut results = Vec::with_capacity(match_ids.len());
+    let total_matches = match_ids.len();
+
+    // Process in controlled batches to prevent system overload
+    for batch in match_ids.chunks(batch_size) {
+        // Load the batch on the shared rayon pool; collect keeps input order
+        let batch_results: Vec<Result<MatchDetail, ProcessorError>> = batch
+            .par_iter()
+            .map(|match_id| get_match_by_id(folder_path, match_id))
+            .collect();
+
+        // Process batch results
+        for result in batch_results {
+            match result {
+                Ok(detail) => {
+                    results.push(detail);
+                    // Report progress after each successful load
+                    progress_callback(results.len(), total_matches);
+                },
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Optional delay between batches to prevent overwhelming slower systems
+        if delay_millis > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(delay_millis));
+        }
+    }
+
+    Ok(results)
+}
+
+/// Load match details in batches, handing each to `on_loaded` as soon as it is parsed
+///
+/// Details arrive in completion order, not request order. Unlike
+/// [`get_multiple_match_details_batched`], a match that fails to load doesn't stop
+/// the rest; its ID is listed in the result instead.
+pub fn stream_match_details(
+    folder_path: Option<&str>,
+    match_ids: &[String],
+    batch_size: usize,
+    on_loaded: impl Fn(MatchDetail) + Sync
+) -> DetailStreamResult {
+    let failed = Mutex::new(Vec::new());
+
+    for batch in match_ids.chunks(batch_size.max(1)) {
+        batch.par_iter().for_each(|match_id| match get_match_by_id(folder_path, match_id) {
+            Ok(detail) => on_loaded(detail),
+            Err(e) => {
+                eprintln!("Failed to load match {}: {}", match_id, e);
+                failed.lock().unwrap().push(match_id.clone());
+            }
+        });
+    }
+
+    let failed_match_ids = failed.into_inner().unwrap();
+    DetailStreamResult {
+        loaded: match_ids.len() - failed_match_ids.len(),
+        failed_match_ids,
+    }
+}
+
+/// Default number of matches loaded per batch
+pub const DEFAULT_DETAIL_BATCH_SIZE: usize = 10;
+
+/// Default pause between batches in milliseconds
+pub const DEFAULT_DETAIL_BATCH_DELAY_MILLIS: u64 = 10;
+
+/// Load multiple match details with a caller-chosen batch size and inter-batch delay
+pub fn get_multiple_match_details_tuned(
+    folder_path: Option<&str>,
+    match_ids: &[String],
+    batch_size: usize,
+    delay_millis: u64
+) -> Result<Vec<MatchDetail>, ProcessorError> {
+    if batch_size == 0 {
+        return Err(ProcessorError::Other("Batch size must be at least 1".to_string()));
+    }
+
+    get_multiple_match_details_batched(folder_path, match_ids, batch_size, delay_millis, |_, _| {})
+}
+
+/// Largest batch size accepted from the frontend
+pub const MAX_DETAIL_BATCH_SIZE: usize = 50;
+
+/// Load multiple match details with an optional cap on how many are parsed at once
+///
+/// `batch_size` is clamped to 1..=[`MAX_DETAIL_BATCH_SIZE`]. Larger batches keep
+/// every core busy for longer between pauses, but hold more parsed matches in
+/// memory at the same time and make progress reports coarser.
+///
+/// `max_parallel` runs the load on a dedicated rayon pool of that many threads.
+/// Fewer threads keep the UI and disk responsive on slow machines at the cost of
+/// total load time. Spinning up the pool costs a little on every call, so leave
+/// it unset to share the global pool, which uses one thread per core.
+pub fn get_multiple_match_details_limited(
+    folder_path: Option<&str>,
+    match_ids: &[String],
+    batch_size: usize,
+    max_parallel: Option<usize>
+) -> Result<Vec<MatchDetail>, ProcessorError> {
+    let batch_size = batch_size.clamp(1, MAX_DETAIL_BATCH_SIZE);
+    let load = || get_multiple_match_details_tuned(folder_path, match_ids, batch_size, DEFAULT_DETAIL_BATCH_DELAY_MILLIS);
+
+    match max_parallel {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.max(1))
+            .build()
+            .map_err(|e| ProcessorError::Other(format!("Failed to start loader threads: {}", e)))?
+            .install(load),
+        None => load(),
+    }
+}
+
+/// Load multiple match details in parallel for better performance (with default batching)
+pub fn get_multiple_match_details(folder_path: Option<&str>, match_ids: &[String]) -> Result<Vec<MatchDetail>, ProcessorError> {
+    // Default batch size balances speed vs system load
+    get_multiple_match_details_tuned(
+        folder_path,
+        match_ids,
+        DEFAULT_DETAIL_BATCH_SIZE,
+        DEFAULT_DETAIL_BATCH_DELAY_MILLIS,
+    )
+}
+
+/// Count kills per weapon across matches, most used first
+///
+/// Kills with an unrecognized or missing weapon are grouped under "Unknown".
+pub fn weapon_stats(folder_path: &str, match_ids: &[String]) -> Result<Vec<WeaponStat>, ProcessorError> {
+    let details = get_multiple_match_details(Some(folder_path), match_ids)?;
+
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for event in details.iter().flat_map(|d| &d.kill_events) {
+        let weapon = event.weapon.clone().unwrap_or_else(|| "Unknown".to_string());
+        *counts.entry(weapon).or_insert(0) += 1;
+    }
+
+    let mut stats: Vec<WeaponStat> = counts
+        .into_iter()
+        .map(|(weapon, kills)| WeaponStat { weapon, kills })
+        .collect();
+
+    // Tie-break on name so equal counts come back in a stable order
+    stats.sort_by(|a, b| b.kills.cmp(&a.kills).then_with(|| a.weapon.cmp(&b.weapon)));
+
+    Ok(stats)
+}
+
+/// Kills per weapon for each team, keyed `team → weapon → kill_count`
+///
+/// Kills are credited to the killer's team; kills by players missing from
+/// `players` are skipped. Missing weapons count as "Unknown", as in [`weapon_stats`].
+pub fn weapon_usage_by_team(events: &[KillEvent], players: &[PlayerStats]) -> HashMap<String, HashMap<String, u32>> {
+    let teams: HashMap<&str, &str> = players
+        .iter()
+        .map(|p| (p.puuid.as_str(), p.team.as_str()))
+        .collect();
+
+    let mut usage: HashMap<String, HashMap<String, u32>> = HashMap::new();
+    for event in events {
+        let Some(team) = teams.get(event.killer_puuid.as_str()) else {
+            continue;
+        };
+        let weapon = event.weapon.clone().unwrap_or_else(|| "Unknown".to_string());
+        *usage.entry(team.to_string()).or_default().entry(weapon).or_insert(0) += 1;
+    }
+
+    usage
+}
+
+/// The weapon each team got the most kills with, ties going to the first name alphabetically
+pub fn most_used_weapon_per_team(events: &[KillEvent], players: &[PlayerStats]) -> HashMap<String, String> {
+    weapon_usage_by_team(events, players)
+        .into_iter()
+        .filter_map(|(team, weapons)| {
+            weapons
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map(|(weapon, _)| (team, weapon))
+        })
+        .collect()
+}
+
+/// Bin kills by time since round start
+///
+/// Buckets run contiguously from 0 to the latest kill so empty stretches show up as zero.
+pub fn kill_timeline(events: &[KillEvent], bucket_millis: i32) -> Result<Vec<TimelineBucket>, ProcessorError> {
+    if bucket_millis <= 0 {
+        return Err(ProcessorError::Other(format!("Bucket size must be positive, got {}", bucket_millis)));
+    }
+
+    let bucket_of = |event: &KillEvent| (event.round_time_millis.max(0) / bucket_millis) as usize;
+
+    let bucket_count = match events.iter().map(bucket_of).max() {
+        Some(last) => last + 1,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut counts = vec![0u32; bucket_count];
+    for event in events {
+        counts[bucket_of(event)] += 1;
+    }
+
+    Ok(counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| TimelineBucket {
+            bucket_start_millis: i as i32 * bucket_millis,
+            count,
+        })
+        .collect())
+}
+
+/// Total rounds played according to a "blue-red" score string
+fn rounds_from_score(score: &str) -> Option<i32> {
+    let (blue, red) = score.split_once('-')?;
+    Some(blue.trim().parse::<i32>().ok()? + red.trim().parse::<i32>().ok()?)
+}
+
+/// Keep only the summaries that satisfy every criterion set on the filter
+pub fn filter_summaries(summaries: &[MatchSummary], filter: &MatchFilter) -> Vec<MatchSummary> {
+    summaries
+        .iter()
+        .filter(|m| {
+            filter.maps.as_ref().is_none_or(|maps| {
+                maps.iter().any(|map| map.eq_ignore_ascii_case(&m.map) || map.eq_ignore_ascii_case(&m.map_display))
+            })
+        })
+        .filter(|m| {
+            filter.regions.as_ref().is_none_or(|regions| {
+                regions.iter().any(|region| region.eq_ignore_ascii_case(&m.region))
+            })
+        })
+        .filter(|m| filter.after.is_none_or(|after| m.game_start.is_some_and(|start| start >= after)))
+        .filter(|m| filter.before.is_none_or(|before| m.game_start.is_some_and(|start| start <= before)))
+        .filter(|m| {
+            filter.min_rounds.is_none_or(|min_rounds| {
+                rounds_from_score(&m.score).is_some_and(|rounds| rounds >= min_rounds)
+            })
+        })
+        .filter(|m| {
+            filter.queues.as_ref().is_none_or(|queues| {
+                m.queue.as_ref().is_none_or(|queue| queues.iter().any(|q| q.eq_ignore_ascii_case(queue)))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Order start times, always placing unknown times after known ones
+fn cmp_game_start(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>, descending: bool) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Sort summaries in place, falling back to match ID so ties come back in a stable order
+pub fn sort_summaries(summaries: &mut [MatchSummary], order: SortOrder) {
+    summaries.sort_by(|a, b| {
+        let primary = match order {
+            // Matches without a valid start time go last in either direction
+            SortOrder::GameStartAsc => cmp_game_start(a.game_start, b.game_start, false),
+            SortOrder::GameStartDesc => cmp_game_start(a.game_start, b.game_start, true),
+            SortOrder::MapAsc => a.map_display.cmp(&b.map_display),
+            SortOrder::RegionAsc => a.region.cmp(&b.region),
+            SortOrder::ScoreAsc => rounds_from_score(&a.score).cmp(&rounds_from_score(&b.score)),
+        };
+        primary.then_with(|| a.match_id.cmp(&b.match_id))
+    });
+}
+
+/// How closely a lowercase query matches a player, from 0 to 1, or None if it doesn't match
+///
+/// The query is compared against both `name` and `name#tag`. An exact match scores 1;
+/// otherwise the score is the share of the matched string the query covers.
+fn player_match_score(query: &str, game_name: &str, tag_line: &str) -> Option<f64> {
+    let name = game_name.to_lowercase();
+    let full = format!("{}#{}", name, tag_line.to_lowercase());
+
+    let matched = if name.contains(query) {
+        &name
+    } else if full.contains(query) {
+        &full
+    } else {
+        return None;
+    };
+
+    Some(query.len() as f64 / matched.len() as f64)
+}
+
+/// Players in the name index whose `name#tag` may contain a lowercase query
+///
+/// The index is keyed by the first characters of the query, so callers still
+/// have to check each candidate against the full query.
+fn player_name_candidates<'a>(index: &'a PlayerIndex, query: &str) -> Vec<(&'a String, &'a PlayerIndexEntry)> {
+    let key: String = query.chars().take(NAME_KEY_LEN).collect();
+    let puuids: std::collections::HashSet<&String> = index.name_prefixes
+        .get(&key)
+        .into_iter()
+        .flatten()
+        .collect();
+
+    puuids
+        .into_iter()
+        .filter_map(|puuid| index.players.get_key_value(puuid))
+        .collect()
+}
+
+/// Find players whose game name contains the query, case-insensitively
+///
+/// Only matches present in `summaries` are counted. Candidates come from the name
+/// index built by the load pass, so the folder is loaded first if it isn't already.
+pub fn search_players(summaries: &[MatchSummary], folder_path: &str, query: &str) -> Result<Vec<PlayerSearchResult>, ProcessorError> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    ensure_folder_loaded(folder_path)?;
+
+    let starts: HashMap<&str, Option<DateTime<Utc>>> = summaries
+        .iter()
+        .map(|m| (m.match_id.as_str(), m.game_start))
+        .collect();
+
+    let index = PLAYER_INDEX.lock().unwrap();
+    let Some(index) = index.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    let mut results: Vec<PlayerSearchResult> = player_name_candidates(index, &query)
+        .into_iter()
+        .filter(|(_, entry)| entry.game_name.to_lowercase().contains(&query))
+        .filter_map(|(puuid, entry)| {
+            let in_scope: Vec<&String> = entry.match_ids
+                .iter()
+                .filter(|id| starts.contains_key(id.as_str()))
+                .collect();
+
+            let most_recent = in_scope.iter().max_by_key(|id| starts[id.as_str()])?;
+
+            Some(PlayerSearchResult {
+                puuid: puuid.clone(),
+                game_name: entry.game_name.clone(),
+                tag_line: entry.tag_line.clone(),
+                matches_found: in_scope.len() as u32,
+                most_recent_match_id: (*most_recent).clone(),
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.matches_found
+            .cmp(&a.matches_found)
+            .then_with(|| a.game_name.cmp(&b.game_name))
+    });
+
+    Ok(results)
+}
+
+/// Find players whose `name` or `name#tag` contains the query, case-insensitively
+///
+/// Each player appears once, ranked by how many loaded matches they appear in,
+/// then by how closely the query matches.
+pub fn search_player_identities(folder_path: &str, query: &str) -> Result<Vec<PlayerIdentity>, ProcessorError> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    ensure_folder_loaded(folder_path)?;
+
+    let index = PLAYER_INDEX.lock().unwrap();
+    let Some(index) = index.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    let mut results: Vec<(PlayerIdentity, f64)> = player_name_candidates(index, &query)
+        .into_iter()
+        .filter_map(|(puuid, entry)| {
+            let score = player_match_score(&query, &entry.game_name, &entry.tag_line)?;
+            Some((
+                PlayerIdentity {
+                    puuid: puuid.clone(),
+                    game_name: entry.game_name.clone(),
+                    tag_line: entry.tag_line.clone(),
+                    match_count: entry.match_ids.len() as u32,
+                },
+                score,
+            ))
+        })
+        .collect();
+
+    results.sort_by(|(a, a_score), (b, b_score)| {
+        b.match_count
+            .cmp(&a.match_count)
+            .then_with(|| b_score.total_cmp(a_score))
+            .then_with(|| a.game_name.cmp(&b.game_name))
+    });
+
+    Ok(results.into_iter().map(|(identity, _)| identity).collect())
+}
+
+/// Every player across the loaded matches, most frequent first
+///
+/// Read from the player index built during loading, so no match details are parsed.
+pub fn list_all_players(folder_path: &str) -> Result<Vec<PlayerListing>, ProcessorError> {
+    ensure_folder_loaded(folder_path)?;
+
+    let mut players: Vec<PlayerListing> = PLAYER_INDEX
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|index| &index.players)
+        .map(|(puuid, entry)| PlayerListing {
+            puuid: puuid.clone(),
+            game_name: entry.game_name.clone(),
+            tag_line: entry.tag_line.clone(),
+            match_count: entry.match_ids.len() as u32,
+        })
+        .collect();
+
+    players.sort_by(|a, b| {
+        b.match_count
+            .cmp(&a.match_count)
+            .then_with(|| a.game_name.cmp(&b.game_name))
+            .then_with(|| a.puuid.cmp(&b.puuid))
+    });
+    Ok(players)
+}
+
+/// Summaries of every loaded match a player appears in, newest first
+///
+/// Uses the player index built during loading, so no match files are reparsed
+/// once the folder is loaded. An unknown PUUID gives an empty list.
+pub fn matches_for_player(folder_path: &str, puuid: &str) -> Result<Vec<MatchSummary>, ProcessorError> {
+    ensure_folder_loaded(folder_path)?;
+
+    let match_ids: std::collections::HashSet<String> = PLAYER_INDEX
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|index| index.players.get(puuid))
+        .map(|entry| entry.match_ids.iter().cloned().collect())
+        .unwrap_or_default();
+
+    let mut summaries: Vec<MatchSummary> = LOADED_MATCHES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|m| match_ids.contains(&m.match_id))
+        .cloned()
+        .collect();
+
+    sort_summaries(&mut summaries, SortOrder::GameStartDesc);
+    Ok(summaries)
+}
+
+/// Summaries of every match currently loaded
+pub fn loaded_summaries() -> Vec<MatchSummary> {
+    LOADED_MATCHES.lock().unwrap().clone()
+}
+
+/// Summaries of the loaded matches stored under a folder, loading it first if needed
+///
+/// Other loaded roots are left out, so the result doesn't depend on what else was opened.
+pub fn folder_summaries(folder_path: &str) -> Result<Vec<MatchSummary>, ProcessorError> {
+    ensure_folder_loaded(folder_path)?;
+
+    let index = MATCH_INDEX.lock().unwrap();
+    let in_folder = |match_id: &str| {
+        index
+            .as_ref()
+            .and_then(|index| index.get(match_id))
+            .is_some_and(|entry| entry.path.starts_with(folder_path))
+    };
+
+    Ok(LOADED_MATCHES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|m| in_folder(&m.match_id))
+        .cloned()
+        .collect())
+}
+
+/// One page of the loaded matches, newest first
+///
+/// The folder is loaded on the first request and the sorted list is kept until
+/// the loaded matches change, so later pages are only a slice of that list.
+/// Pages are numbered from 0; a page past the end comes back empty.
+pub fn load_matches_page(
+    folder_path: &str,
+    cache_dir: Option<&Path>,
+    page: usize,
+    page_size: usize
+) -> Result<MatchPage, ProcessorError> {
+    if page_size == 0 {
+        return Err(ProcessorError::Other("Page size must be at least 1".to_string()));
+    }
+
+    let folder_loaded = LOADED_ROOTS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|root| root == Path::new(folder_path));
+    if !folder_loaded {
+        load_json_files(folder_path, cache_dir, &LoadOptions::default())?;
+    }
+
+    let mut sorted = SORTED_MATCHES.lock().unwrap();
+    let sorted = sorted.get_or_insert_with(|| {
+        let mut summaries = LOADED_MATCHES.lock().unwrap().clone();
+        sort_summaries(&mut summaries, SortOrder::default());
+        summaries
+    });
+
+    let start = page.saturating_mul(page_size).min(sorted.len());
+    let end = start.saturating_add(page_size).min(sorted.len());
+
+    Ok(MatchPage {
+        summaries: sorted[start..end].to_vec(),
+        total: sorted.len(),
+        page,
+        page_size,
+    })
+}
+
+/// Load a folder unless it is already one of the loaded roots
+pub fn ensure_folder_loaded(folder_path: &str) -> Result<(), ProcessorError> {
+    let folder_loaded = LOADED_ROOTS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|root| root == Path::new(folder_path));
+
+    if !folder_loaded {
+        load_json_files(folder_path, None, &LoadOptions::default())?;
+    }
+
+    Ok(())
+}
+
+/// Collect the distinct maps, regions and date range of the matches in a folder
+pub fn get_facets(folder_path: &str) -> Result<Facets, ProcessorError> {
+    let summaries = folder_summaries(folder_path)?;
+
+    let mut maps: Vec<String> = summaries.iter().map(|m| m.map_display.clone()).collect();
+    maps.sort();
+    maps.dedup();
+
+    let mut regions: Vec<String> = summaries.iter().map(|m| m.region.clone()).collect();
+    regions.sort();
+    regions.dedup();
+
+    Ok(Facets {
+        maps,
+        regions,
+        date_min: summaries.iter().filter_map(|m| m.game_start).min(),
+        date_max: summaries.iter().filter_map(|m| m.game_start).max(),
+    })
+}
+
+/// The first kill of each round, in round order
+///
+/// Rounds are told apart by number only, so pass one match's events at a time.
+/// Kills at the same round time keep the one listed first.
+pub fn extract_opening_duels(events: &[KillEvent]) -> Vec<&KillEvent> {
+    let mut openings: HashMap<i32, &KillEvent> = HashMap::new();
+    for event in events {
+        openings
+            .entry(event.round_num)
+            .and_modify(|opening| {
+                if event.round_time_millis < opening.round_time_millis {
+                    *opening = event;
+                }
+            })
+            .or_insert(event);
+    }
+
+    let mut openings: Vec<&KillEvent> = openings.into_values().collect();
+    openings.sort_by_key(|e| e.round_num);
+    openings
+}
+
+/// Find kills that avenge a teammate killed within `window_millis` earlier in the same round
+pub fn detect_trades(events: &[KillEvent], players: &[PlayerStats], window_millis: i32) -> Vec<TradeKill> {
+    let teams: HashMap<&str, &str> = players
+        .iter()
+        .map(|p| (p.puuid.as_str(), p.team_id.as_str()))
+        .collect();
+
+    // Kill events are grouped by player, not time, so put them in round order first
+    let mut ordered: Vec<&KillEvent> = events.iter().collect();
+    ordered.sort_by_key(|e| (e.round_num, e.round_time_millis));
+
+    let mut trades = Vec::new();
+
+    for (i, trade) in ordered.iter().enumerate() {
+        let trader_team = match teams.get(trade.killer_puuid.as_str()) {
+            Some(team) => *team,
+            None => continue,
+        };
+
+        // Most recent earlier kill in this round where the traded player killed a teammate
+        let traded = ordered[..i].iter().rev().find(|earlier| {
+            earlier.round_num == trade.round_num
+                && trade.round_time_millis - earlier.round_time_millis <= window_millis
+                && earlier.killer_puuid == trade.victim_puuid
+                && earlier.victim_puuid != trade.killer_puuid
+                && teams.get(earlier.victim_puuid.as_str()) == Some(&trader_team)
+        });
+
+        if let Some(traded) = traded {
+            trades.push(TradeKill {
+                trade: (*trade).clone(),
+                traded: (*traded).clone(),
+            });
+        }
+    }
+
+    trades
+}
+
+/// Rank players by `(kills + assists / 2) / deaths`, best first
+///
+/// Deaths are floored at 1 so deathless players still rank. Observers are left out.
+pub fn kda_leaderboard(players: &[PlayerStats]) -> Vec<PlayerKda> {
+    let mut leaderboard: Vec<PlayerKda> = players
+        .iter()
+        .filter(|p| !p.is_observer)
+        .map(|p| PlayerKda {
+            puuid: p.puuid.clone(),
+            game_name: p.game_name.clone(),
+            kda_ratio: (p.kills as f64 + p.assists as f64 / 2.0) / p.deaths.max(1) as f64,
+        })
+        .collect();
+
+    leaderboard.sort_by(|a, b| b.kda_ratio.total_cmp(&a.kda_ratio));
+    leaderboard
+}
+
+/// Player with the highest combat score, ignoring observers
+pub fn mvp_player(players: &[PlayerStats]) -> Option<&PlayerStats> {
+    players
+        .iter()
+        .filter(|p| !p.is_observer)
+        .max_by_key(|p| p.score)
+}
+
+/// Team that won the most rounds in a match, or None for a draw or missing round data
+fn map_winner(detail: &MatchDetail) -> Option<String> {
+    let mut round_wins: HashMap<&str, u32> = HashMap::new();
+    for team in detail.rounds.iter().filter_map(|r| r.winning_team.as_deref()) {
+        *round_wins.entry(team).or_insert(0) += 1;
+    }
+
+    let best = round_wins.values().copied().max()?;
+    let mut leaders = round_wins.iter().filter(|(_, &wins)| wins == best);
+    let (team, _) = leaders.next()?;
+    if leaders.next().is_some() {
+        return None;
+    }
+    Some(team.to_string())
+}
+
+/// Combine the given matches into series totals, in the order of `match_ids`
+///
+/// Details whose match ID is not listed are ignored.
+pub fn aggregate_series(details: &[MatchDetail], match_ids: &[String]) -> SeriesStats {
+    let mut stats = SeriesStats {
+        series_id: String::new(),
+        match_ids: Vec::new(),
+        total_rounds: 0,
+        maps_played: Vec::new(),
+        team_scores: HashMap::new(),
+    };
+
+    for match_id in match_ids {
+        let Some(detail) = details.iter().find(|d| &d.match_id == match_id) else {
+            continue;
+        };
+
+        stats.match_ids.push(detail.match_id.clone());
+        stats.total_rounds += detail.rounds_played.max(0) as u32;
+        stats.maps_played.push(detail.map_display.clone());
+
+        if let Some(winner) = map_winner(detail) {
+            *stats.team_scores.entry(winner).or_insert(0) += 1;
+        }
+    }
+
+    stats
+}
+
+/// Find the players who appear in both matches and pair up their kills and deaths,
+/// alongside side-by-side round wins, kill totals and per-weapon kill deltas
+pub fn compare_matches(a: &MatchDetail, b: &MatchDetail) -> MatchComparison {
+    let players_b: HashMap<&str, &PlayerStats> = b.players
+        .iter()
+        .map(|p| (p.puuid.as_str(), p))
+        .collect();
+
+    let mut shared_puuids = Vec::new();
+    let mut player_kda_diff = HashMap::new();
+
+    for player_a in &a.players {
+        if let Some(player_b) = players_b.get(player_a.puuid.as_str()) {
+            shared_puuids.push(player_a.puuid.clone());
+            player_kda_diff.insert(player_a.puuid.clone(), KdaDiff {
+                kills_a: player_a.kills,
+                kills_b: player_b.kills,
+                deaths_a: player_a.deaths,
+                deaths_b: player_b.deaths,
+            });
+        }
+    }
+
+    shared_puuids.sort();
+
+    let rounds_won = |detail: &MatchDetail, team: &str| {
+        detail.rounds
+            .iter()
+            .filter(|r| r.winning_team.as_deref() == Some(team))
+            .count() as u32
+    };
+
+    let mut weapon_kill_deltas: HashMap<String, i32> = HashMap::new();
+    for (detail, sign) in [(a, -1), (b, 1)] {
+        for event in &detail.kill_events {
+            let weapon = event.weapon.clone().unwrap_or_else(|| "Unknown".to_string());
+            *weapon_kill_deltas.entry(weapon).or_insert(0) += sign;
+        }
+    }
+
+    MatchComparison {
+        shared_puuids,
+        match_a_id: a.match_id.clone(),
+        match_b_id: b.match_id.clone(),
+        player_kda_diff,
+        blue_rounds: (rounds_won(a, "Blue"), rounds_won(b, "Blue")),
+        red_rounds: (rounds_won(a, "Red"), rounds_won(b, "Red")),
+        total_kills: (a.kill_events.len() as u32, b.kill_events.len() as u32),
+        weapon_kill_deltas,
+    }
+}
+
+/// Sum kills into equal-width buckets covering the whole match
+///
+/// Events without a game time are placed by assuming rounds of equal length.
+pub fn compute_kill_timeline(events: &[KillEvent], game_length_millis: i64, bucket_size_millis: u64) -> KillTimeline {
+    let mut timeline = KillTimeline {
+        buckets_millis: Vec::new(),
+        killer_counts: Vec::new(),
+        victim_counts: Vec::new(),
+    };
+
+    if bucket_size_millis == 0 {
+        return timeline;
+    }
+
+    let rounds = events.iter().map(|e| e.round_num + 1).max().unwrap_or(1).max(1) as i64;
+    let average_round_millis = game_length_millis.max(0) / rounds;
+
+    let times: Vec<u64> = events
+        .iter()
+        .map(|e| {
+            let estimated = e.round_num as i64 * average_round_millis + e.round_time_millis as i64;
+            e.game_time_millis.unwrap_or(estimated).max(0) as u64
+        })
+        .collect();
+
+    // Span the reported game length, stretching to cover any kill recorded after it
+    let span = times
+        .iter()
+        .copied()
+        .max()
+        .map_or(0, |t| t + 1)
+        .max(game_length_millis.max(0) as u64);
+    let bucket_count = span.div_ceil(bucket_size_millis) as usize;
+
+    timeline.buckets_millis = (0..bucket_count as u64).map(|i| i * bucket_size_millis).collect();
+    timeline.killer_counts = vec![0; bucket_count];
+    timeline.victim_counts = vec![0; bucket_count];
+
+    for (event, time) in events.iter().zip(times) {
+        let bucket = (time / bucket_size_millis) as usize;
+
+        timeline.victim_counts[bucket] += 1;
+        if !event.killer_puuid.is_empty() && event.killer_puuid != event.victim_puuid {
+            timeline.killer_counts[bucket] += 1;
+        }
+    }
+
+    timeline
+}
+
+/// Find rounds where a player was the last alive on their team against at least two opponents
+///
+/// Alive counts are rebuilt from each round's kills in time order. A won clutch
+/// with `got_final_kill` set is a 1vX the player closed out themselves.
+pub fn detect_clutches(detail: &MatchDetail) -> Vec<ClutchEvent> {
+    let winners: HashMap<i32, &str> = detail.rounds
+        .iter()
+        .filter_map(|r| r.winning_team.as_deref().map(|team| (r.round_num, team)))
+        .collect();
+
+    let teams: HashMap<&str, &str> = detail.players
+        .iter()
+        .filter(|p| !p.is_observer)
+        .map(|p| (p.puuid.as_str(), p.team_id.as_str()))
+        .collect();
+
+    let mut events_by_round: HashMap<i32, Vec<&KillEvent>> = HashMap::new();
+    for event in &detail.kill_events {
+        events_by_round.entry(event.round_num).or_default().push(event);
+    }
+
+    let mut round_nums: Vec<i32> = events_by_round.keys().copied().collect();
+    round_nums.sort();
+
+    let mut clutches = Vec::new();
+
+    for round_num in round_nums {
+        let mut events = events_by_round.remove(&round_num).unwrap_or_default();
+        events.sort_by_key(|e| e.round_time_millis);
+        let final_killer = events.last().map(|e| e.killer_puuid.as_str());
+
+        // Everyone on a team starts the round alive
+        let mut alive: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (puuid, team) in &teams {
+            alive.entry(*team).or_default().push(*puuid);
+        }
+
+        for event in &events {
+            if let Some(team) = teams.get(event.victim_puuid.as_str()) {
+                if let Some(members) = alive.get_mut(team) {
+                    members.retain(|puuid| *puuid != event.victim_puuid);
+                }
+            }
+
+            let clutch = alive.iter().find_map(|(team, members)| {
+                let opponents: usize = alive
+                    .iter()
+                    .filter(|(other, _)| *other != team)
+                    .map(|(_, others)| others.len())
+                    .sum();
+                (members.len() == 1 && opponents >= 2).then(|| (*team, members[0], opponents))
+            });
+
+            if let Some((team, puuid, opponents)) = clutch {
+                clutches.push(ClutchEvent {
+                    round_num,
+                    clutch_player_puuid: puuid.to_string(),
+                    opponents_remaining: opponents as u32,
+                    won: winners.get(&round_num) == Some(&team),
+                    got_final_kill: final_killer == Some(puuid),
+                });
+                // Only the moment the clutch starts counts; later kills don't start a new one
+                break;
+            }
+        }
+    }
+
+    clutches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[test]
+    fn second_lookup_is_served_from_cache() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        let first = get_match_by_id(Some(folder), "m1").unwrap();
+        let reads = DETAIL_READS.load(Ordering::SeqCst);
+        let second = get_match_by_id(Some(folder), "m1").unwrap();
+
+        assert_eq!(DETAIL_READS.load(Ordering::SeqCst), reads);
+        assert_eq!(first.match_id, second.match_id);
+        assert_eq!(first.kill_events.len(), second.kill_events.len());
+    }
+
+    #[test]
+    fn matches_resolve_from_every_loaded_root() {
+        let _guard = lock_global_state();
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        write_match(first.path(), "a.json", &simple_match("from-first"));
+        write_match(second.path(), "b.json", &simple_match("from-second"));
+
+        let roots = vec![path_str(first.path()).to_string(), path_str(second.path()).to_string()];
+        let result = load_json_files_multi(&roots, None, &LoadOptions::default()).unwrap();
+        assert_eq!(result.summaries.len(), 2);
+
+        assert_eq!(get_match_by_id(None, "from-first").unwrap().match_id, "from-first");
+        assert_eq!(get_match_by_id(None, "from-second").unwrap().match_id, "from-second");
+    }
+
+    #[test]
+    fn duplicate_matches_across_roots_are_kept_once() {
+        let _guard = lock_global_state();
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        write_match(first.path(), "a.json", &simple_match("shared"));
+        write_match(second.path(), "a.json", &simple_match("shared"));
+
+        let roots = vec![path_str(first.path()).to_string(), path_str(second.path()).to_string()];
+        let result = load_json_files_multi(&roots, None, &LoadOptions::default()).unwrap();
+
+        assert_eq!(result.summaries.len(), 1);
+        assert_eq!(result.duplicates_skipped, 1);
+    }
+
+    #[test]
+    fn weapon_stats_sum_kills_across_matches() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let first = vec![
+            kill("b0", "r0", 5_000, (1000, -3000)),
+            with_weapon(kill("b1", "r1", 6_000, (1000, -3000)), OPERATOR),
+        ];
+        let second = vec![
+            kill("r0", "b0", 5_000, (1000, -3000)),
+            with_weapon(kill("r1", "b1", 6_000, (1000, -3000)), PHANTOM),
+            with_weapon(kill("r2", "b2", 7_000, (1000, -3000)), "00000000-0000-0000-0000-000000000000"),
+        ];
+        write_match(dir.path(), "m1.json", &vct_match("m1", ten_players(), vec![round(0, "Blue", first)]));
+        write_match(dir.path(), "m2.json", &vct_match("m2", ten_players(), vec![round(0, "Red", second)]));
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        let stats = weapon_stats(folder, &["m1".to_string(), "m2".to_string()]).unwrap();
+        let counts: Vec<(&str, u32)> = stats.iter().map(|s| (s.weapon.as_str(), s.kills)).collect();
+
+        assert_eq!(counts, vec![("Vandal", 2), ("Operator", 1), ("Phantom", 1), ("Unknown", 1)]);
+    }
+
+    #[test]
+    fn kill_timeline_bins_by_round_time() {
+        let events = kill_events(vec![round(0, "Blue", vec![
+            kill("b0", "r0", 1_000, (1000, -3000)),
+            kill("b1", "r1", 1_500, (1000, -3000)),
+            kill("b2", "r2", 6_000, (1000, -3000)),
+        ])]);
+
+        let buckets = kill_timeline(&events, 5_000).unwrap();
+        let counts: Vec<(i32, u32)> = buckets.iter().map(|b| (b.bucket_start_millis, b.count)).collect();
+
+        assert_eq!(counts, vec![(0, 2), (5_000, 1)]);
+    }
+
+    #[test]
+    fn kill_timeline_rejects_non_positive_buckets() {
+        assert!(kill_timeline(&[], 0).is_err());
+        assert!(kill_timeline(&[], -5_000).is_err());
+    }
+
+    /// Overwrite a file without changing its modification time, so only a reparse would notice
+    fn rewrite_keeping_mtime(path: &Path, contents: &str) {
+        let mtime = fs::metadata(path).unwrap().modified().unwrap();
+        fs::write(path, contents).unwrap();
+        File::options().write(true).open(path).unwrap().set_modified(mtime).unwrap();
+    }
+
+    #[test]
+    fn unchanged_files_are_served_from_summary_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_dir = tempfile::tempdir().unwrap();
+        let file = write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+        let mut db = SummaryDb::open(&summary_db_path(db_dir.path())).unwrap();
+
+        let first = load_folder(folder, Some(&mut db), &LoadOptions::default(), |_, _| {}).unwrap();
+        assert_eq!(first.result.summaries.len(), 1);
+
+        // Unparseable contents only go unnoticed if the file is skipped
+        rewrite_keeping_mtime(&file, "{ not json");
+        let second = load_folder(folder, Some(&mut db), &LoadOptions::default(), |_, _| {}).unwrap();
+        assert_eq!(second.result.parse_errors, 0);
+        assert_eq!(second.result.summaries[0].match_id, "m1");
+    }
+
+    #[test]
+    fn modified_files_are_reparsed_and_upserted() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_dir = tempfile::tempdir().unwrap();
+        let file = write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+        let mut db = SummaryDb::open(&summary_db_path(db_dir.path())).unwrap();
+        load_folder(folder, Some(&mut db), &LoadOptions::default(), |_, _| {}).unwrap();
+
+        fs::write(&file, simple_match("m1-edited").to_string()).unwrap();
+        let later = fs::metadata(&file).unwrap().modified().unwrap() + std::time::Duration::from_secs(60);
+        File::options().write(true).open(&file).unwrap().set_modified(later).unwrap();
+
+        let reloaded = load_folder(folder, Some(&mut db), &LoadOptions::default(), |_, _| {}).unwrap();
+        assert_eq!(reloaded.result.summaries[0].match_id, "m1-edited");
+
+        let cached = db.load_all().unwrap();
+        assert_eq!(cached[&file].1[0].summary.match_id, "m1-edited");
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mmap_and_buffered_reads_give_the_same_detail() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write_match(dir.path(), "m1.json", &simple_match("m1"));
+
+        let mapped: VctMatchData = read_json_mmap(&file).unwrap();
+        let buffered: VctMatchData = read_match_file(&file).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(parse_match_detail(&file, &mapped)).unwrap(),
+            serde_json::to_value(parse_match_detail(&file, &buffered)).unwrap()
+        );
+    }
+
+    #[test]
+    fn use_mmap_does_not_change_loaded_summaries() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+
+        let buffered = load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        let mapped = load_json_files(folder, None, &LoadOptions { use_mmap: true, ..LoadOptions::default() }).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&buffered.summaries).unwrap(),
+            serde_json::to_value(&mapped.summaries).unwrap()
+        );
+    }
+
+    fn summary(match_id: &str, map_display: &str, region: &str, start_secs: i64, score: &str) -> MatchSummary {
+        MatchSummary {
+            match_id: match_id.to_string(),
+            map: format!("/Game/Maps/{0}/{0}", map_display),
+            map_display: map_display.to_string(),
+            region: region.to_string(),
+            game_start: Utc.timestamp_opt(start_secs, 0).single(),
+            game_start_valid: true,
+            teams: vec!["Blue".to_string(), "Red".to_string()],
+            team_sizes: HashMap::new(),
+            score: score.to_string(),
+            rounds_counted: rounds_from_score(score).unwrap_or(0),
+            game_length_millis: None,
+            game_length_display: None,
+            queue: None,
+            game_version: None,
+            is_valid_match: true,
+        }
+    }
+
+    fn filter_fixture() -> Vec<MatchSummary> {
+        vec![
+            summary("early-ascent", "Ascent", "EMEA", 1_000, "13-5"),
+            summary("mid-bind", "Bind", "Americas", 2_000, "13-11"),
+            summary("late-ascent", "Ascent", "Pacific", 3_000, "14-12"),
+        ]
+    }
+
+    fn filtered_ids(filter: &MatchFilter) -> Vec<String> {
+        filter_summaries(&filter_fixture(), filter).into_iter().map(|m| m.match_id).collect()
+    }
+
+    #[test]
+    fn filter_by_map() {
+        let filter = MatchFilter { maps: Some(vec!["ascent".to_string()]), ..MatchFilter::default() };
+        assert_eq!(filtered_ids(&filter), vec!["early-ascent", "late-ascent"]);
+    }
+
+    #[test]
+    fn filter_by_region() {
+        let filter = MatchFilter { regions: Some(vec!["Americas".to_string()]), ..MatchFilter::default() };
+        assert_eq!(filtered_ids(&filter), vec!["mid-bind"]);
+    }
+
+    #[test]
+    fn filter_by_date_range() {
+        let after = MatchFilter { after: Utc.timestamp_opt(2_000, 0).single(), ..MatchFilter::default() };
+        assert_eq!(filtered_ids(&after), vec!["mid-bind", "late-ascent"]);
+
+        let before = MatchFilter { before: Utc.timestamp_opt(1_500, 0).single(), ..MatchFilter::default() };
+        assert_eq!(filtered_ids(&before), vec!["early-ascent"]);
+    }
+
+    #[test]
+    fn filter_by_min_rounds() {
+        let filter = MatchFilter { min_rounds: Some(24), ..MatchFilter::default() };
+        assert_eq!(filtered_ids(&filter), vec!["mid-bind", "late-ascent"]);
+    }
+
+    #[test]
+    fn filter_dimensions_combine() {
+        let filter = MatchFilter {
+            maps: Some(vec!["Ascent".to_string()]),
+            after: Utc.timestamp_opt(500, 0).single(),
+            min_rounds: Some(20),
+            ..MatchFilter::default()
+        };
+        assert_eq!(filtered_ids(&filter), vec!["late-ascent"]);
+        assert_eq!(filtered_ids(&MatchFilter::default()).len(), 3);
+    }
+
+    #[test]
+    fn observer_only_matches_are_flagged_invalid() {
+        let players = vec![player("caster-0", "Neutral"), player("caster-1", "Neutral")];
+        let data: VctMatchSummaryData = serde_json::from_value(vct_match("casters", players, Vec::new())).unwrap();
+
+        let summary = parse_match_summary(Path::new("casters.json"), &data);
+
+        assert!(!summary.is_valid_match);
+        assert!(summary.teams.is_empty());
+    }
+
+    #[test]
+    fn matches_with_team_players_are_valid() {
+        let data: VctMatchSummaryData = serde_json::from_value(simple_match("m1")).unwrap();
+        assert!(parse_match_summary(Path::new("m1.json"), &data).is_valid_match);
+    }
+
+    #[test]
+    fn game_start_desc_returns_newest_first() {
+        let mut summaries = vec![
+            summary("middle", "Ascent", "EMEA", 2_000, "13-5"),
+            summary("oldest", "Ascent", "EMEA", 1_000, "13-5"),
+            summary("newest", "Ascent", "EMEA", 3_000, "13-5"),
+        ];
+
+        sort_summaries(&mut summaries, SortOrder::default());
+        let ids: Vec<&str> = summaries.iter().map(|m| m.match_id.as_str()).collect();
+
+        assert_eq!(ids, vec!["newest", "middle", "oldest"]);
+    }
+
+    #[test]
+    fn missing_game_length_parses_as_unknown() {
+        let mut value = simple_match("m1");
+        value["matchInfo"].as_object_mut().unwrap().remove("gameLengthMillis");
+
+        let data = parse_match_json(&value.to_string()).unwrap();
+        assert_eq!(data.match_info.game_length_millis, None);
+        assert_eq!(parse_match_detail(Path::new("m1.json"), &data).game_length_formatted, "Unknown");
+    }
+
+    #[test]
+    fn game_length_formats_the_same_in_summary_and_detail() {
+        let mut value = simple_match("m1");
+        value["matchInfo"]["gameLengthMillis"] = serde_json::json!(2_535_999);
+        let data = parse_match_json(&value.to_string()).unwrap();
+        let summary_data: VctMatchSummaryData = serde_json::from_value(value).unwrap();
+
+        let detail = parse_match_detail(Path::new("m1.json"), &data);
+        let summary = parse_match_summary(Path::new("m1.json"), &summary_data);
+
+        assert_eq!(detail.game_length_formatted, "42m 15s");
+        assert_eq!(summary.game_length_display.as_deref(), Some("42m 15s"));
+    }
+
+    #[test]
+    fn trades_are_detected_only_within_the_window() {
+        let detail = parse_detail(&vct_match("trades", ten_players(), vec![
+            // b1 avenges b0 two seconds later
+            round(0, "Blue", vec![
+                kill("r0", "b0", 10_000, (1000, -3000)),
+                kill("b1", "r0", 12_000, (1000, -3000)),
+            ]),
+            // b3 gets r1 long after r1 killed b2
+            round(1, "Red", vec![
+                kill("r1", "b2", 10_000, (1000, -3000)),
+                kill("b3", "r1", 20_000, (1000, -3000)),
+            ]),
+        ]));
+
+        let trades = detect_trades(&detail.kill_events, &detail.players, 3_000);
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade.killer_puuid, "b1");
+        assert_eq!(trades[0].traded.victim_puuid, "b0");
+        assert_eq!(trades[0].trade.round_num, 0);
+    }
+
+    fn player_with_kd(puuid: &str, team: &str, kills: i32, deaths: i32) -> serde_json::Value {
+        let mut player = player(puuid, team);
+        player["stats"]["kills"] = serde_json::json!(kills);
+        player["stats"]["deaths"] = serde_json::json!(deaths);
+        player
+    }
+
+    #[test]
+    fn compare_matches_pairs_up_shared_players() {
+        let a = parse_detail(&vct_match("a", vec![
+            player_with_kd("shared", "Blue", 20, 10),
+            player_with_kd("only-a", "Red", 5, 15),
+        ], Vec::new()));
+        let b = parse_detail(&vct_match("b", vec![
+            player_with_kd("shared", "Red", 12, 18),
+            player_with_kd("only-b", "Blue", 9, 9),
+        ], Vec::new()));
+
+        let comparison = compare_matches(&a, &b);
+
+        assert_eq!(comparison.shared_puuids, vec!["shared"]);
+        assert_eq!((comparison.match_a_id.as_str(), comparison.match_b_id.as_str()), ("a", "b"));
+        let diff = &comparison.player_kda_diff["shared"];
+        assert_eq!((diff.kills_a, diff.kills_b, diff.deaths_a, diff.deaths_b), (20, 12, 10, 18));
+    }
+
+    #[test]
+    fn known_map_paths_use_their_display_name() {
+        assert_eq!(normalize_map_name("/Game/Maps/Ascent/Ascent"), "Ascent");
+        assert_eq!(normalize_map_name("/Game/Maps/Bonsai/Bonsai"), "Split");
+    }
+
+    #[test]
+    fn unknown_map_paths_fall_back_to_the_last_segment() {
+        assert_eq!(normalize_map_name("/Game/Maps/Unreleased/Skyline"), "Skyline");
+    }
+
+    #[test]
+    fn kill_timeline_buckets_sum_to_total_kills() {
+        let events = kill_events(vec![
+            round(0, "Blue", vec![kill("b0", "r0", 5_000, (1000, -3000)), kill("r1", "b1", 40_000, (1000, -3000))]),
+            round(1, "Red", vec![kill("r2", "b2", 20_000, (1000, -3000))]),
+            round(2, "Blue", vec![kill("b3", "r3", 90_000, (1000, -3000))]),
+        ]);
+
+        let timeline = compute_kill_timeline(&events, 300_000, 60_000);
+
+        assert_eq!(timeline.buckets_millis.len(), 5);
+        assert_eq!(timeline.victim_counts.iter().sum::<u32>() as usize, events.len());
+        assert_eq!(timeline.killer_counts.iter().sum::<u32>() as usize, events.len());
+    }
+
+    #[test]
+    fn reader_path_parses_large_files_like_the_string_path() {
+        let locations: Vec<serde_json::Value> = (0..10)
+            .map(|i| serde_json::json!({ "puuid": format!("p{}", i), "location": { "x": 1000 + i * 10, "y": -3000 } }))
+            .collect();
+        let rounds = (0..200)
+            .map(|r| {
+                let kills = (0..5)
+                    .map(|k| {
+                        let mut kill = kill(&format!("b{}", k), &format!("r{}", k), k * 1_000, (1000 + r, -3000));
+                        kill["playerLocations"] = serde_json::json!(locations);
+                        kill
+                    })
+                    .collect();
+                round(r, "Blue", kills)
+            })
+            .collect();
+        let raw = vct_match("large", ten_players(), rounds).to_string();
+        let path = Path::new("large.json");
+
+        let from_reader = parse_match_detail_from_reader(path, BufReader::new(raw.as_bytes())).unwrap();
+        let from_string = parse_match_detail(path, &parse_match_json(&raw).unwrap());
+
+        assert_eq!(from_reader.kill_events.len(), 1_000);
+        assert_eq!(serde_json::to_value(from_reader).unwrap(), serde_json::to_value(from_string).unwrap());
+    }
+
+    #[test]
+    fn facets_list_distinct_maps_and_regions() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let mut split = simple_match("split");
+        split["matchInfo"]["map"] = serde_json::json!("/Game/Maps/Bonsai/Bonsai");
+        split["matchInfo"]["gameStartMillis"] = serde_json::json!(1_700_100_000_000i64);
+        write_match(dir.path(), "EMEA/ascent-1.json", &simple_match("ascent-1"));
+        write_match(dir.path(), "EMEA/split.json", &split);
+        write_match(dir.path(), "PACIFIC/ascent-2.json", &simple_match("ascent-2"));
+
+        let facets = get_facets(path_str(dir.path())).unwrap();
+
+        assert_eq!(facets.maps, vec!["Ascent", "Split"]);
+        assert_eq!(facets.regions, vec!["EMEA", "PACIFIC"]);
+        assert_eq!(facets.date_min, Utc.timestamp_millis_opt(1_700_000_000_000).single());
+        assert_eq!(facets.date_max, Utc.timestamp_millis_opt(1_700_100_000_000).single());
+    }
+
+    #[test]
+    fn won_clutch_is_detected() {
+        let detail = parse_detail(&vct_match("clutch", ten_players(), vec![clutch_round(0, 3, true)]));
+
+        let clutches = detect_clutches(&detail);
+
+        assert_eq!(clutches.len(), 1);
+        assert_eq!(clutches[0].clutch_player_puuid, "b4");
+        assert_eq!(clutches[0].opponents_remaining, 3);
+        assert!(clutches[0].won);
+        assert!(clutches[0].got_final_kill);
+    }
+
+    #[test]
+    fn lost_clutch_is_detected() {
+        let detail = parse_detail(&vct_match("clutch", ten_players(), vec![clutch_round(0, 2, false)]));
+
+        let clutches = detect_clutches(&detail);
+
+        assert_eq!(clutches.len(), 1);
+        assert_eq!(clutches[0].opponents_remaining, 2);
+        assert!(!clutches[0].won);
+        assert!(!clutches[0].got_final_kill);
+    }
+
+    #[test]
+    fn rounds_past_regulation_count_as_overtime() {
+        let rounds = (0..26).map(|r| round(r, if r % 2 == 0 { "Blue" } else { "Red" }, Vec::new())).collect();
+
+        let detail = parse_detail(&vct_match("overtime", ten_players(), rounds));
+
+        assert_eq!(detail.overtime_rounds, 2);
+        assert_eq!(round_phase(11), RoundPhase::FirstHalf);
+        assert_eq!(round_phase(12), RoundPhase::SecondHalf);
+        assert_eq!(round_phase(24), RoundPhase::Overtime);
+    }
+
+    /// Write `count` simple matches and return their IDs in reverse name order
+    fn write_matches(dir: &Path, count: usize) -> Vec<String> {
+        let ids: Vec<String> = (0..count).map(|i| format!("m{:02}", i)).rev().collect();
+        for id in &ids {
+            write_match(dir, &format!("{}.json", id), &simple_match(id));
+        }
+        ids
+    }
+
+    #[test]
+    fn tuned_loads_keep_request_order_for_any_batch_size() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let ids = write_matches(dir.path(), 7);
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        for batch_size in [1, 3, 7, 20] {
+            clear_match_cache();
+            let details = get_multiple_match_details_tuned(Some(folder), &ids, batch_size, 0).unwrap();
+            let loaded: Vec<&String> = details.iter().map(|d| &d.match_id).collect();
+            assert_eq!(loaded, ids.iter().collect::<Vec<_>>(), "batch size {}", batch_size);
+        }
+
+        assert!(get_multiple_match_details_tuned(Some(folder), &ids, 0, 0).is_err());
+    }
+
+    #[test]
+    fn batched_loads_of_fifty_matches_keep_request_order() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let ids = write_matches(dir.path(), 50);
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        let progress = Mutex::new(Vec::new());
+
+        let details = get_multiple_match_details_batched(Some(folder), &ids, 8, 0, |done, total| {
+            progress.lock().unwrap().push((done, total));
+        })
+        .unwrap();
+
+        let loaded: Vec<&String> = details.iter().map(|d| &d.match_id).collect();
+        assert_eq!(loaded, ids.iter().collect::<Vec<_>>());
+        let progress = progress.into_inner().unwrap();
+        assert_eq!(progress.len(), 50);
+        assert_eq!(progress.last(), Some(&(50, 50)));
+    }
+
+    /// Category of a kill with the given finishing damage JSON
+    fn category_of(finishing_damage: serde_json::Value) -> KillCategory {
+        let mut kill = kill("b0", "r0", 1_000, (1000, -3000));
+        kill["finishingDamage"] = finishing_damage;
+        kill_events(vec![round(0, "Blue", vec![kill])])[0].kill_category
+    }
+
+    #[test]
+    fn gun_kills_are_categorized() {
+        assert_eq!(category_of(serde_json::json!({ "damageType": "Weapon", "damageItem": VANDAL })), KillCategory::Gun);
+        assert_eq!(category_of(serde_json::json!({ "damageItem": OPERATOR })), KillCategory::Gun);
+    }
+
+    #[test]
+    fn ability_kills_are_categorized() {
+        assert_eq!(category_of(serde_json::json!({ "damageType": "Ability", "damageItem": "Ultimate" })), KillCategory::Ability);
+        assert_eq!(category_of(serde_json::json!({ "damageItem": "4ADE7FAA-4CF1-8376-95EF-39884480959B" })), KillCategory::Ability);
+    }
+
+    #[test]
+    fn melee_kills_are_categorized() {
+        assert_eq!(category_of(serde_json::json!({ "damageType": "Melee", "damageItem": "" })), KillCategory::Melee);
+        assert_eq!(category_of(serde_json::json!({ "damageItem": "2F59173C-4BED-B6C3-2191-DEA9B58E9CF7" })), KillCategory::Melee);
+    }
+
+    #[test]
+    fn bomb_kills_are_categorized() {
+        assert_eq!(category_of(serde_json::json!({ "damageType": "Bomb", "damageItem": "" })), KillCategory::Bomb);
+    }
+
+    #[test]
+    fn fall_kills_are_categorized() {
+        assert_eq!(category_of(serde_json::json!({ "damageType": "Fall", "damageItem": "" })), KillCategory::Fall);
+    }
+
+    #[test]
+    fn unresolved_kills_are_unknown() {
+        assert_eq!(category_of(serde_json::json!({ "damageItem": "00000000-0000-0000-0000-000000000000" })), KillCategory::Unknown);
+        assert_eq!(category_of(serde_json::Value::Null), KillCategory::Unknown);
+    }
+
+    #[test]
+    fn round_kills_returns_only_that_round() {
+        let events = kill_events(vec![
+            round(0, "Blue", vec![kill("b0", "r0", 1_000, (1000, -3000)), kill("b1", "r1", 2_000, (1000, -3000))]),
+            round(1, "Red", vec![kill("r2", "b2", 3_000, (1000, -3000))]),
+        ]);
+
+        let kills = round_kills(&events, 0);
+
+        assert_eq!(kills.len(), 2);
+        assert!(kills.iter().all(|e| e.round_num == 0));
+    }
+
+    #[test]
+    fn round_kills_is_empty_for_a_round_without_kills() {
+        let events = kill_events(vec![round(0, "Blue", vec![kill("b0", "r0", 1_000, (1000, -3000))]), round(1, "Red", Vec::new())]);
+        assert!(round_kills(&events, 1).is_empty());
+    }
+
+    #[test]
+    fn ndjson_files_yield_one_summary_per_line() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let lines = format!("{}\n\n{}\n", simple_match("line-1"), simple_match("line-2"));
+        fs::write(dir.path().join("matches.jsonl"), lines).unwrap();
+        let folder = path_str(dir.path());
+
+        let result = load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        let mut ids: Vec<&str> = result.summaries.iter().map(|m| m.match_id.as_str()).collect();
+        ids.sort();
+
+        assert_eq!(ids, vec!["line-1", "line-2"]);
+        assert_eq!(result.parse_errors, 0);
+        assert_eq!(get_match_by_id(Some(folder), "line-2").unwrap().match_id, "line-2");
+    }
+
+    #[test]
+    fn missing_folder_is_folder_not_found() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+
+        let result = load_json_files(path_str(&missing), None, &LoadOptions::default());
+
+        assert!(matches!(result, Err(ProcessorError::FolderNotFound(folder)) if folder == path_str(&missing)));
+    }
+
+    #[test]
+    fn unknown_match_is_match_not_found() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        let result = get_match_by_id(Some(folder), "no-such-match");
+
+        assert!(matches!(result, Err(ProcessorError::MatchNotFound(id)) if id == "no-such-match"));
+    }
+
+    #[test]
+    fn gzipped_files_parse_like_plain_ones() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let value = simple_match("m1");
+        let plain = write_match(dir.path(), "m1.json", &value);
+        let gzipped = dir.path().join("m1.json.gz");
+        let mut encoder = GzEncoder::new(File::create(&gzipped).unwrap(), flate2::Compression::default());
+        encoder.write_all(value.to_string().as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let from_gzip: VctMatchData = read_match_file(&gzipped).unwrap();
+        let from_plain: VctMatchData = read_match_file(&plain).unwrap();
+
+        assert!(is_match_file(&gzipped));
+        assert_eq!(
+            serde_json::to_value(parse_match_detail(&plain, &from_gzip)).unwrap(),
+            serde_json::to_value(parse_match_detail(&plain, &from_plain)).unwrap()
+        );
+    }
+
+    #[test]
+    fn assist_perspective_plots_only_located_assistants() {
+        let mut assisted = with_killer_at(kill("b0", "r0", 1_000, (1000, -3000)), (1500, -3500));
+        assisted["assistants"] = serde_json::json!(["b1", "b2"]);
+        assisted["playerLocations"].as_array_mut().unwrap().push(serde_json::json!({ "puuid": "b1", "location": { "x": 2000, "y": -4000 } }));
+        let data = match_data(&vct_match("assists", ten_players(), vec![round(0, "Blue", vec![assisted])]));
+
+        let (kills, _) = extract_kills(&data.round_results, &HashMap::new());
+        let (kill, event) = &kills[0];
+        let locations = perspective_locations(kill, event, Perspective::Assists);
+
+        assert_eq!(event.assistants, vec!["b1", "b2"]);
+        assert_eq!(locations.len(), 1);
+        assert_eq!((locations[0].x, locations[0].y), (2000, -4000));
+    }
+
+    #[test]
+    fn compare_matches_diffs_scores_kills_and_weapons() {
+        let a = parse_detail(&vct_match("a", ten_players(), vec![
+            round(0, "Blue", vec![kill("b0", "r0", 1_000, (1000, -3000))]),
+            round(1, "Blue", vec![with_weapon(kill("b0", "r1", 1_000, (1000, -3000)), OPERATOR)]),
+        ]));
+        let b = parse_detail(&vct_match("b", ten_players(), vec![
+            round(0, "Red", vec![kill("r0", "b0", 1_000, (1000, -3000)), kill("r1", "b1", 2_000, (1000, -3000))]),
+            round(1, "Red", vec![kill("r2", "b2", 1_000, (1000, -3000))]),
+            round(2, "Blue", Vec::new()),
+        ]));
+
+        let comparison = compare_matches(&a, &b);
+
+        assert_eq!(comparison.blue_rounds, (2, 1));
+        assert_eq!(comparison.red_rounds, (0, 2));
+        assert_eq!(comparison.total_kills, (2, 3));
+        assert_eq!(comparison.weapon_kill_deltas["Vandal"], 2);
+        assert_eq!(comparison.weapon_kill_deltas["Operator"], -1);
+    }
+
+    #[test]
+    fn summary_data_skips_rounds_but_agrees_with_the_full_parse() {
+        let value = vct_match("full", ten_players(), vec![
+            round(0, "Blue", vec![with_killer_at(kill("b0", "r0", 1_000, (1000, -3000)), (1500, -3500))]),
+            round(1, "Red", vec![kill("r0", "b0", 1_000, (1000, -3000))]),
+            round(2, "Blue", Vec::new()),
+        ]);
+        let path = Path::new("full.json");
+
+        let summary = parse_match_summary_from_reader(path, value.to_string().as_bytes()).unwrap();
+        let detail = parse_match_detail(path, &match_data(&value));
+
+        assert_eq!(summary.match_id, detail.match_id);
+        assert_eq!(summary.map_display, detail.map_display);
+        assert_eq!(summary.game_start, detail.game_start);
+        assert_eq!(summary.score, "2-1");
+        assert_eq!(summary.rounds_counted, detail.rounds_played);
+        assert_eq!(summary.game_length_millis, detail.game_length_millis);
+    }
+
+    #[test]
+    fn team_sizes_leave_out_observers() {
+        let mut players = ten_players();
+        players.push(player("caster", "Neutral"));
+        let data: VctMatchSummaryData = serde_json::from_value(vct_match("sizes", players, Vec::new())).unwrap();
+
+        let summary = parse_match_summary(Path::new("sizes.json"), &data);
+
+        assert_eq!(summary.team_sizes.len(), 2);
+        assert_eq!(summary.team_sizes["Blue"], 5);
+        assert_eq!(summary.team_sizes["Red"], 5);
+    }
+
+    #[test]
+    fn scanning_an_unloaded_folder_for_an_unknown_match_is_match_not_found() {
+        let _guard = lock_global_state();
+        let loaded = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        write_match(loaded.path(), "m1.json", &simple_match("m1"));
+        write_match(other.path(), "m2.json", &simple_match("m2"));
+        load_json_files(path_str(loaded.path()), None, &LoadOptions::default()).unwrap();
+
+        assert_eq!(get_match_by_id(Some(path_str(other.path())), "m2").unwrap().match_id, "m2");
+        let result = get_match_by_id(Some(path_str(other.path())), "missing");
+        assert!(matches!(result, Err(ProcessorError::MatchNotFound(id)) if id == "missing"));
+    }
+
+    #[test]
+    fn read_errors_map_to_their_variants() {
+        let dir = tempfile::tempdir().unwrap();
+        let bad_json = dir.path().join("bad.json");
+        fs::write(&bad_json, "{ not json").unwrap();
+
+        assert!(matches!(read_match_file::<VctMatchData>(&dir.path().join("missing.json")), Err(ProcessorError::FileRead(_))));
+        assert!(matches!(read_match_file::<VctMatchData>(&bad_json), Err(ProcessorError::Parse(_))));
+        assert!(matches!(parse_match_json(r#"{ "players": [] }"#), Err(ProcessorError::InvalidData(_))));
+    }
+
+    #[test]
+    fn kills_are_annotated_with_the_round_outcome() {
+        let mut players = ten_players();
+        players.retain(|p| p["puuid"] != "b4");
+        let events = parse_detail(&vct_match("outcome", players, vec![round(0, "Blue", vec![
+            kill("b0", "r0", 1_000, (1000, -3000)),
+            kill("r1", "b1", 2_000, (1000, -3000)),
+            // b4 isn't on the roster, so their team can't be resolved
+            kill("b4", "r2", 3_000, (1000, -3000)),
+        ])])).kill_events;
+
+        let outcome = |killer: &str| events.iter().find(|e| e.killer_puuid == killer).unwrap().round_won_by_killer;
+        assert_eq!(outcome("b0"), Some(true));
+        assert_eq!(outcome("r1"), Some(false));
+        assert_eq!(outcome("b4"), None);
+    }
+
+    fn named_player(puuid: &str, team: &str, game_name: &str, tag_line: &str) -> serde_json::Value {
+        let mut player = player(puuid, team);
+        player["gameName"] = serde_json::json!(game_name);
+        player["tagLine"] = serde_json::json!(tag_line);
+        player
+    }
+
+    /// Load a folder where "TenZ#SEN" plays two matches and "Tenzing#NA" one
+    fn load_named_players(dir: &Path) {
+        let tenz = named_player("tenz", "Blue", "TenZ", "SEN");
+        let tenzing = named_player("tenzing", "Red", "Tenzing", "NA");
+        write_match(dir, "m1.json", &vct_match("m1", vec![tenz.clone(), tenzing], Vec::new()));
+        write_match(dir, "m2.json", &vct_match("m2", vec![tenz, player("other", "Red")], Vec::new()));
+        load_json_files(path_str(dir), None, &LoadOptions::default()).unwrap();
+    }
+
+    fn identity_names(folder: &str, query: &str) -> Vec<String> {
+        search_player_identities(folder, query)
+            .unwrap()
+            .into_iter()
+            .map(|p| format!("{}#{}:{}", p.game_name, p.tag_line, p.match_count))
+            .collect()
+    }
+
+    #[test]
+    fn player_search_finds_an_exact_name() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        load_named_players(dir.path());
+
+        assert_eq!(identity_names(path_str(dir.path()), "tenzing"), vec!["Tenzing#NA:1"]);
+    }
+
+    #[test]
+    fn player_search_ranks_partial_matches_by_match_count() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        load_named_players(dir.path());
+
+        assert_eq!(identity_names(path_str(dir.path()), "TEN"), vec!["TenZ#SEN:2", "Tenzing#NA:1"]);
+    }
+
+    #[test]
+    fn player_search_matches_name_and_tag() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        load_named_players(dir.path());
+
+        assert_eq!(identity_names(path_str(dir.path()), "tenz#sen"), vec!["TenZ#SEN:2"]);
+        assert!(identity_names(path_str(dir.path()), "tenz#na").is_empty());
+    }
+
+    #[test]
+    fn matches_for_player_lists_only_their_matches() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        load_named_players(dir.path());
+        let folder = path_str(dir.path());
+
+        let ids = |puuid: &str| -> Vec<String> {
+            matches_for_player(folder, puuid).unwrap().into_iter().map(|m| m.match_id).collect()
+        };
+
+        assert_eq!(ids("tenzing"), vec!["m1"]);
+        assert_eq!(ids("tenz"), vec!["m1", "m2"]);
+        assert!(ids("nobody").is_empty());
+    }
+
+    #[test]
+    fn absurd_start_times_are_flagged_and_sorted_last() {
+        let mut broken = simple_match("broken");
+        broken["matchInfo"]["gameStartMillis"] = serde_json::json!(i64::MAX);
+        let data: VctMatchSummaryData = serde_json::from_value(broken).unwrap();
+
+        let broken = parse_match_summary(Path::new("broken.json"), &data);
+        assert_eq!(broken.game_start, None);
+        assert!(!broken.game_start_valid);
+
+        let mut summaries = vec![broken, summary("dated", "Ascent", "EMEA", 1_000, "13-5")];
+        sort_summaries(&mut summaries, SortOrder::GameStartDesc);
+        assert_eq!(summaries[1].match_id, "broken");
+    }
+
+    #[test]
+    fn raw_json_is_returned_for_a_known_match() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+
+        let raw = get_raw_match_json(folder, "m1").unwrap();
+
+        assert_eq!(raw, fs::read_to_string(path).unwrap());
+        assert!(matches!(get_raw_match_json(folder, "missing"), Err(ProcessorError::MatchNotFound(_))));
+    }
+
+    #[test]
+    fn raw_json_over_the_cap_is_rejected() {
+        let oversized = vec![b' '; MAX_RAW_JSON_BYTES as usize + 1];
+        assert!(read_capped(oversized.as_slice(), Path::new("big.json")).is_err());
+    }
+
+    #[test]
+    fn queue_id_is_parsed_when_present() {
+        let mut value = simple_match("ranked");
+        value["matchInfo"]["queueId"] = serde_json::json!("competitive");
+        let with_queue: VctMatchSummaryData = serde_json::from_value(value).unwrap();
+        let without_queue: VctMatchSummaryData = serde_json::from_value(simple_match("plain")).unwrap();
+
+        assert_eq!(parse_match_summary(Path::new("ranked.json"), &with_queue).queue.as_deref(), Some("competitive"));
+        assert_eq!(parse_match_summary(Path::new("plain.json"), &without_queue).queue, None);
+    }
+
+    #[test]
+    fn queue_filter_keeps_matches_without_a_queue() {
+        let mut custom = summary("custom", "Ascent", "EMEA", 1_000, "13-5");
+        custom.queue = Some("custom".to_string());
+        let mut ranked = summary("ranked", "Ascent", "EMEA", 2_000, "13-5");
+        ranked.queue = Some("competitive".to_string());
+        let unknown = summary("unknown", "Ascent", "EMEA", 3_000, "13-5");
+        let filter = MatchFilter { queues: Some(vec!["Competitive".to_string()]), ..MatchFilter::default() };
+
+        let kept: Vec<String> = filter_summaries(&[custom, ranked, unknown], &filter).into_iter().map(|m| m.match_id).collect();
+
+        assert_eq!(kept, vec!["ranked", "unknown"]);
+    }
+
+    #[test]
+    fn streaming_hands_over_one_detail_per_requested_match() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let ids = write_matches(dir.path(), 12);
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        let loaded = Mutex::new(Vec::new());
+
+        let result = stream_match_details(Some(folder), &ids, 5, |detail| loaded.lock().unwrap().push(detail.match_id));
+
+        let mut loaded = loaded.into_inner().unwrap();
+        loaded.sort();
+        let mut expected = ids.clone();
+        expected.sort();
+        assert_eq!(loaded, expected);
+        assert_eq!(result.loaded, 12);
+        assert!(result.failed_match_ids.is_empty());
+    }
+
+    #[test]
+    fn streaming_reports_matches_that_fail_to_load() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let mut ids = write_matches(dir.path(), 2);
+        ids.push("missing".to_string());
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        let result = stream_match_details(Some(folder), &ids, 10, |_| {});
+
+        assert_eq!(result.loaded, 2);
+        assert_eq!(result.failed_match_ids, vec!["missing"]);
+    }
+
+    #[test]
+    fn refresh_picks_up_an_edited_file() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        assert_eq!(get_match_by_id(Some(folder), "m1").unwrap().kill_events.len(), 1);
+
+        let kills = vec![kill("b0", "r0", 10_000, (1000, -3000)), kill("r1", "b1", 12_000, (1000, -3000))];
+        let mut edited = vct_match("m1", ten_players(), vec![round(0, "Red", kills)]);
+        edited["matchInfo"]["gameLengthMillis"] = serde_json::json!(1_800_000);
+        fs::write(&path, edited.to_string()).unwrap();
+
+        let summary = refresh_match(folder, "m1").unwrap();
+        assert_eq!(summary.game_length_millis, Some(1_800_000));
+        assert_eq!(summary.score, "0-1");
+        assert_eq!(get_match_by_id(Some(folder), "m1").unwrap().kill_events.len(), 2);
+    }
+
+    #[test]
+    fn refreshing_a_deleted_file_drops_it_from_the_index() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_match(dir.path(), "m1.json", &simple_match("m1"));
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(refresh_match(folder, "m1"), Err(ProcessorError::FileRead(_))));
+        assert!(matches!(refresh_match(folder, "m1"), Err(ProcessorError::MatchNotFound(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_skipped_unless_followed() {
+        let _guard = lock_global_state();
+        let outside = tempfile::tempdir().unwrap();
+        let linked_file = write_match(outside.path(), "linked.json", &simple_match("linked-file"));
+        write_match(outside.path(), "nested/inner.json", &simple_match("linked-dir"));
+        let dir = tempfile::tempdir().unwrap();
+        write_match(dir.path(), "m1.json", &simple_match("m1"));
+        std::os::unix::fs::symlink(&linked_file, dir.path().join("linked.json")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("nested"), dir.path().join("nested")).unwrap();
+        let folder = path_str(dir.path());
+
+        let skipped = load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        let ids: Vec<&str> = skipped.summaries.iter().map(|s| s.match_id.as_str()).collect();
+        assert_eq!(ids, vec!["m1"]);
+        assert_eq!(validate_vct_folder(folder, false).valid_vct_files, 1);
+
+        let options = LoadOptions { follow_symlinks: true, ..LoadOptions::default() };
+        let followed = load_json_files(folder, None, &options).unwrap();
+        assert_eq!(followed.summaries.len(), 3);
+        assert_eq!(validate_vct_folder(folder, true).valid_vct_files, 3);
+    }
+
+    #[test]
+    fn unexpected_game_version_loads_with_a_warning() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let mut future = simple_match("future");
+        future["matchInfo"]["gameVersion"] = serde_json::json!("release-99.01-shipping-1-1");
+        write_match(dir.path(), "future.json", &future);
+        write_match(dir.path(), "current.json", &simple_match("current"));
+
+        let result = load_json_files(path_str(dir.path()), None, &LoadOptions::default()).unwrap();
+
+        assert_eq!(result.summaries.len(), 2);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("future"));
+        assert!(result.warnings[0].contains("release-99.01"));
+        let future = result.summaries.iter().find(|s| s.match_id == "future").unwrap();
+        assert_eq!(future.game_version.as_deref(), Some("release-99.01-shipping-1-1"));
+    }
+
+    #[test]
+    fn empty_folder_reports_no_matches_without_rescanning() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a match").unwrap();
+        let folder = path_str(dir.path());
+
+        let result = load_json_files(folder, None, &LoadOptions::default()).unwrap();
+        assert!(result.no_matches_found);
+        assert!(result.summaries.is_empty());
+
+        // Written after the load, so only a rescan could find it
+        write_match(dir.path(), "late.json", &simple_match("late"));
+        assert!(matches!(get_match_by_id(Some(folder), "late"), Err(ProcessorError::MatchNotFound(_))));
+        assert!(matches!(get_match_by_id(None, "late"), Err(ProcessorError::MatchNotFound(_))));
+    }
+
+    #[test]
+    fn rounds_are_classified_by_plant_and_winner() {
+        let planted = |round_num: i32, winner: &str| {
+            let mut planted = round(round_num, winner, vec![kill("r0", "b0", 10_000, (1000, -3000))]);
+            planted["bombPlanter"] = serde_json::json!("r0");
+            planted["plantRoundTime"] = serde_json::json!(40_000);
+            planted["plantSite"] = serde_json::json!("A");
+            planted
+        };
+        let mut retaken = planted(1, "Blue");
+        retaken["bombDefuser"] = serde_json::json!("b1");
+        retaken["defuseRoundTime"] = serde_json::json!(75_000);
+        let rounds = vec![
+            planted(0, "Red"),
+            retaken,
+            round(2, "Blue", vec![kill("b0", "r0", 10_000, (1000, -3000))]),
+        ];
+
+        let detail = parse_detail(&vct_match("types", ten_players(), rounds));
+
+        assert_eq!(detail.round_types, vec![RoundType::Execute, RoundType::Retake, RoundType::NoPlant]);
+    }
+
+    #[test]
+    fn kills_with_absurd_coordinates_are_dropped_and_counted() {
+        let _guard = lock_global_state();
+        set_max_coordinate_magnitude(DEFAULT_MAX_COORDINATE_MAGNITUDE);
+        let rounds = vec![round(0, "Blue", vec![
+            kill("b0", "r0", 10_000, (1000, -3000)),
+            kill("b0", "r1", 12_000, (5_000_000, -3000)),
+        ])];
+
+        let detail = parse_detail(&vct_match("absurd", ten_players(), rounds));
+
+        assert_eq!(detail.kill_events.len(), 1);
+        assert_eq!(detail.kill_events[0].victim_puuid, "r0");
+        assert_eq!(detail.dropped_kill_events, 1);
+    }
+
+    #[test]
+    fn rounds_without_a_known_winner_are_left_out_of_the_score() {
+        let mut no_winner = round(1, "Blue", Vec::new());
+        no_winner["winningTeam"] = serde_json::Value::Null;
+        let rounds = vec![
+            round(0, "Blue", Vec::new()),
+            no_winner,
+            round(2, "Neutral", Vec::new()),
+            round(3, "Red", Vec::new()),
+            round(4, "Blue", Vec::new()),
+        ];
+        let data: VctMatchSummaryData = serde_json::from_value(vct_match("scored", ten_players(), rounds)).unwrap();
+
+        let summary = parse_match_summary(Path::new("scored.json"), &data);
+
+        assert_eq!(summary.score, "2-1");
+        assert_eq!(summary.rounds_counted, 3);
+    }
+
+    #[test]
+    fn normalized_exports_report_missing_round_data() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        let normalized = serde_json::to_value(parse_detail(&simple_match("normalized"))).unwrap();
+        write_match(dir.path(), "normalized.json", &normalized);
+        let folder = path_str(dir.path());
+        load_json_files(folder, None, &LoadOptions::default()).unwrap();
+
+        assert_eq!(get_match_by_id(Some(folder), "normalized").unwrap().kill_events.len(), 1);
+        assert!(matches!(get_match_rounds(Some(folder), "normalized"), Err(ProcessorError::InvalidData(_))));
+    }
+
+    #[test]
+    fn facets_only_cover_the_requested_folder() {
+        let _guard = lock_global_state();
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        write_match(first.path(), "a.json", &simple_match("on-ascent"));
+        let mut bind = simple_match("on-bind");
+        bind["matchInfo"]["map"] = serde_json::json!("/Game/Maps/Duality/Duality");
+        write_match(second.path(), "b.json", &bind);
+        let roots = vec![path_str(first.path()).to_string(), path_str(second.path()).to_string()];
+        load_json_files_multi(&roots, None, &LoadOptions::default()).unwrap();
+
+        assert_eq!(get_facets(path_str(first.path())).unwrap().maps, vec!["Ascent"]);
+        assert_eq!(get_facets(path_str(second.path())).unwrap().maps, vec!["Bind"]);
+    }
 }
@@ -1,3 +1,16 @@
 // Library module for Tauri application
 pub mod models;
-pub mod json_processor;
\ No newline at end of file
+pub mod error;
+pub mod json_processor;
+pub mod app_state;
+pub mod summary_db;
+pub mod watcher;
+pub mod coordinates;
+pub mod analytics;
+pub mod zones;
+pub mod heatmap;
+pub mod export;
+pub mod tags;
+pub mod utils;
+#[cfg(test)]
+mod test_support;
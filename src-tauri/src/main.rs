@@ -2,14 +2,30 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod models;
+mod error;
 mod json_processor;
+mod app_state;
+mod summary_db;
+mod watcher;
+mod coordinates;
+mod analytics;
+mod zones;
+mod heatmap;
+mod export;
+mod tags;
+mod utils;
+#[cfg(test)]
+mod test_support;
 
-use models::{MatchSummary, MatchDetail};
-use tauri::Manager;
+use models::{AgentStat, AgentStats, AppState, BatchExportResult, BlendMode, BlendedHeatmap, Bounds, Clutch, ClutchEvent, Colormap, DangerPosition, DetailStreamResult, DiffHeatmap, DistributionHistogram, Facets, FolderValidation, LoadOptions, GridCell, GridFlip, HeadToHead, KillCluster, KillEvent, KillGraph, KillSnapshot, KillTimeline, LoadResult, MapCalibration, MatchComparison, MatchDetail, MatchFilter, MatchMomentum, MatchPage, MatchSummary, Perspective, PlayerCentroid, PlayerIdentity, PlayerKda, PlayerListing, PlayerSearchResult, ReplayEvent, RoundOutcome, RoundPhase, SeriesStats, SortOrder, SpatialCluster, TimelineBucket, TradeKill, TrajectoryLine, UserPrefs, WeaponUsageReport, WeaponStat, WeightMode};
+use error::ProcessorError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{Emitter, Manager};
 
 /// Select a folder using native file picker
 #[tauri::command]
-async fn select_folder(app: tauri::AppHandle) -> Result<String, String> {
+async fn select_folder(app: tauri::AppHandle) -> Result<String, ProcessorError> {
     use tauri_plugin_dialog::DialogExt;
     
     let folder = app.dialog()
@@ -18,50 +34,785 @@ async fn select_folder(app: tauri::AppHandle) -> Result<String, String> {
     
     match folder {
         Some(path) => Ok(path.to_string()),
-        None => Err("No folder selected".to_string()),
+        None => Err(ProcessorError::Cancelled),
     }
 }
 
+/// Select a zip archive of match files using native file picker
+#[tauri::command]
+async fn select_zip_file(app: tauri::AppHandle) -> Result<String, ProcessorError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let file = app.dialog()
+        .file()
+        .add_filter("Zip archives", &["zip"])
+        .blocking_pick_file();
+
+    match file {
+        Some(path) => Ok(path.to_string()),
+        None => Err(ProcessorError::Cancelled),
+    }
+}
+
+/// Check that a folder holds parseable match files before loading it
+#[tauri::command]
+fn validate_folder(folder_path: String, follow_symlinks: Option<bool>) -> Result<FolderValidation, ProcessorError> {
+    if !Path::new(&folder_path).is_dir() {
+        return Err(ProcessorError::FolderNotFound(folder_path));
+    }
+    Ok(json_processor::validate_vct_folder(&folder_path, follow_symlinks.unwrap_or(false)))
+}
+
+/// Resolve where match indexes are cached between restarts
+fn index_cache_dir(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok()
+}
+
 /// Load all JSON match files from a folder
 #[tauri::command]
-fn load_matches(folder_path: String) -> Result<Vec<MatchSummary>, String> {
-    json_processor::load_json_files(&folder_path)
+fn load_matches(
+    app: tauri::AppHandle,
+    folder_path: String,
+    options: Option<LoadOptions>,
+    sort: Option<SortOrder>
+) -> Result<LoadResult, ProcessorError> {
+    let cache_dir = index_cache_dir(&app);
+    let mut result = json_processor::load_json_files(&folder_path, cache_dir.as_deref(), &options.unwrap_or_default())?;
+    json_processor::sort_summaries(&mut result.summaries, sort.unwrap_or_default());
+    Ok(result)
 }
 
-/// Load all JSON match files
+/// Get one page of a folder's matches, newest first, loading the folder on first use
 #[tauri::command]
-fn load_matches_with_progress(folder_path: String) -> Result<Vec<MatchSummary>, String> {
-    json_processor::load_json_files_with_progress(&folder_path, |_, _| {
-        // Progress callback - could be used for logging or other purposes
+fn load_matches_page(app: tauri::AppHandle, folder_path: String, page: usize, page_size: usize) -> Result<MatchPage, ProcessorError> {
+    let cache_dir = index_cache_dir(&app);
+    json_processor::load_matches_page(&folder_path, cache_dir.as_deref(), page, page_size)
+}
+
+/// Load all JSON match files from a folder without blocking the async runtime
+///
+/// The walk and parse run on the blocking thread pool. Progress is emitted as
+/// `load-progress` events carrying `(processed, total)`.
+#[tauri::command]
+async fn load_matches_async(
+    app: tauri::AppHandle,
+    folder_path: String,
+    options: Option<LoadOptions>,
+    sort: Option<SortOrder>
+) -> Result<LoadResult, ProcessorError> {
+    let cache_dir = index_cache_dir(&app);
+    let options = options.unwrap_or_default();
+
+    let mut result = load_folder_in_background(folder_path, cache_dir, options, move |processed, total| {
+        if let Err(e) = app.emit("load-progress", (processed, total)) {
+            eprintln!("Failed to emit load-progress event: {}", e);
+        }
+    })
+    .await?;
+
+    json_processor::sort_summaries(&mut result.summaries, sort.unwrap_or_default());
+    Ok(result)
+}
+
+/// Load a folder on the blocking thread pool, reporting `(processed, total)` files as it goes
+async fn load_folder_in_background(
+    folder_path: String,
+    cache_dir: Option<PathBuf>,
+    options: LoadOptions,
+    progress_callback: impl Fn(usize, usize) + Send + Sync + 'static
+) -> Result<LoadResult, ProcessorError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        json_processor::load_json_files_multi_with_progress(&[folder_path], cache_dir.as_deref(), &options, progress_callback)
     })
+    .await
+    .map_err(|e| ProcessorError::Other(format!("Folder load task failed: {}", e)))?
+}
+
+/// Load all JSON match files
+#[tauri::command]
+fn load_matches_with_progress(
+    app: tauri::AppHandle,
+    folder_path: String,
+    sort: Option<SortOrder>,
+    follow_symlinks: Option<bool>
+) -> Result<LoadResult, ProcessorError> {
+    // Goes through the indexed loader so match lookups afterwards don't fall back to scanning
+    let cache_dir = index_cache_dir(&app);
+    let options = LoadOptions {
+        follow_symlinks: follow_symlinks.unwrap_or(false),
+        ..LoadOptions::default()
+    };
+    let mut result = json_processor::load_json_files(&folder_path, cache_dir.as_deref(), &options)?;
+    json_processor::sort_summaries(&mut result.summaries, sort.unwrap_or_default());
+    Ok(result)
+}
+
+/// Load JSON match files from several folders into one session
+#[tauri::command]
+fn load_matches_multi(
+    app: tauri::AppHandle,
+    folder_paths: Vec<String>,
+    options: Option<LoadOptions>,
+    sort: Option<SortOrder>
+) -> Result<LoadResult, ProcessorError> {
+    let cache_dir = index_cache_dir(&app);
+    let mut result = json_processor::load_json_files_multi(&folder_paths, cache_dir.as_deref(), &options.unwrap_or_default())?;
+    json_processor::sort_summaries(&mut result.summaries, sort.unwrap_or_default());
+    Ok(result)
+}
+
+/// Load every JSON match file inside a zip archive
+#[tauri::command]
+fn load_matches_from_zip(zip_path: String, sort: Option<SortOrder>) -> Result<Vec<MatchSummary>, ProcessorError> {
+    let mut summaries = json_processor::load_from_zip(Path::new(&zip_path))?;
+    json_processor::sort_summaries(&mut summaries, sort.unwrap_or_default());
+    Ok(summaries)
+}
+
+/// Load all JSON match files from a folder, keeping only those matching the filter
+#[tauri::command]
+fn load_matches_filtered(
+    app: tauri::AppHandle,
+    folder_path: String,
+    filter: MatchFilter
+) -> Result<Vec<MatchSummary>, ProcessorError> {
+    let cache_dir = index_cache_dir(&app);
+    let result = json_processor::load_json_files(&folder_path, cache_dir.as_deref(), &LoadOptions::default())?;
+    let mut summaries = json_processor::filter_summaries(&result.summaries, &filter);
+    json_processor::sort_summaries(&mut summaries, filter.sort.unwrap_or_default());
+    Ok(summaries)
+}
+
+/// Get the maps, regions and date range present in a folder
+#[tauri::command]
+fn get_facets_cmd(folder_path: String) -> Result<Facets, ProcessorError> {
+    json_processor::get_facets(&folder_path)
+}
+
+/// Discard cached summaries and indexes and reparse every loaded folder
+#[tauri::command]
+fn rebuild_index(app: tauri::AppHandle) -> Result<LoadResult, ProcessorError> {
+    let cache_dir = index_cache_dir(&app);
+    json_processor::rebuild_index(cache_dir.as_deref(), &LoadOptions::default())
 }
 
 /// Get detailed match information by ID
 #[tauri::command]
-fn get_match_detail(folder_path: String, match_id: String) -> Result<MatchDetail, String> {
-    json_processor::get_match_by_id(&folder_path, &match_id)
+fn get_match_detail(
+    folder_path: Option<String>,
+    match_id: String,
+    round_phase: Option<RoundPhase>
+) -> Result<MatchDetail, ProcessorError> {
+    let detail = json_processor::get_match_by_id(folder_path.as_deref(), &match_id)?;
+    Ok(json_processor::filter_detail_by_phase(detail, round_phase))
 }
 
 /// Get multiple match details in parallel for better performance
 #[tauri::command]
-fn get_multiple_match_details(folder_path: String, match_ids: Vec<String>) -> Result<Vec<MatchDetail>, String> {
-    json_processor::get_multiple_match_details(&folder_path, &match_ids)
+fn get_multiple_match_details(
+    folder_path: Option<String>,
+    match_ids: Vec<String>,
+    round_phase: Option<RoundPhase>,
+    batch_size: Option<u32>,
+    max_parallel: Option<u32>
+) -> Result<Vec<MatchDetail>, ProcessorError> {
+    let details = json_processor::get_multiple_match_details_limited(
+        folder_path.as_deref(),
+        &match_ids,
+        batch_size.map_or(json_processor::DEFAULT_DETAIL_BATCH_SIZE, |size| size as usize),
+        max_parallel.map(|threads| threads as usize),
+    )?;
+    Ok(details
+        .into_iter()
+        .map(|detail| json_processor::filter_detail_by_phase(detail, round_phase))
+        .collect())
+}
+
+/// Get multiple match details with an explicit batch size and delay between batches
+#[tauri::command]
+fn get_multiple_match_details_tuned(
+    folder_path: Option<String>,
+    match_ids: Vec<String>,
+    batch_size: usize,
+    delay_millis: u64
+) -> Result<Vec<MatchDetail>, ProcessorError> {
+    json_processor::get_multiple_match_details_tuned(folder_path.as_deref(), &match_ids, batch_size, delay_millis)
 }
 
 /// Get multiple match details with progress updates (controlled batching)
 #[tauri::command]
 fn get_multiple_match_details_with_progress(
-    folder_path: String,
-    match_ids: Vec<String>
-) -> Result<Vec<MatchDetail>, String> {
+    folder_path: Option<String>,
+    match_ids: Vec<String>,
+    round_phase: Option<RoundPhase>
+) -> Result<Vec<MatchDetail>, ProcessorError> {
     // For now, just use the regular batch loading without events
     // This avoids permission issues while still providing controlled loading
-    json_processor::get_multiple_match_details(&folder_path, &match_ids)
+    let details = json_processor::get_multiple_match_details(folder_path.as_deref(), &match_ids)?;
+    Ok(details
+        .into_iter()
+        .map(|detail| json_processor::filter_detail_by_phase(detail, round_phase))
+        .collect())
+}
+
+/// Load match details in the background, emitting each one as soon as it is parsed
+///
+/// Every loaded match is sent as a `match-detail-loaded` event carrying its
+/// `MatchDetail`, in completion order. A final `match-details-complete` event
+/// carries the same result the command returns.
+#[tauri::command]
+async fn stream_match_details(
+    app: tauri::AppHandle,
+    folder_path: Option<String>,
+    match_ids: Vec<String>,
+    round_phase: Option<RoundPhase>
+) -> Result<DetailStreamResult, ProcessorError> {
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let result = json_processor::stream_match_details(
+            folder_path.as_deref(),
+            &match_ids,
+            json_processor::DEFAULT_DETAIL_BATCH_SIZE,
+            |detail| {
+                let detail = json_processor::filter_detail_by_phase(detail, round_phase);
+                if let Err(e) = app.emit("match-detail-loaded", &detail) {
+                    eprintln!("Failed to emit match-detail-loaded event: {}", e);
+                }
+            },
+        );
+
+        if let Err(e) = app.emit("match-details-complete", &result) {
+            eprintln!("Failed to emit match-details-complete event: {}", e);
+        }
+        result
+    })
+    .await
+    .map_err(|e| ProcessorError::Other(format!("Match detail task failed: {}", e)))?;
+
+    Ok(result)
+}
+
+/// Search players by game name across all loaded matches
+#[tauri::command]
+fn search_players(folder_path: String, query: String) -> Result<Vec<PlayerSearchResult>, ProcessorError> {
+    json_processor::search_players(&json_processor::loaded_summaries(), &folder_path, &query)
+}
+
+/// Search players by game name or `name#tag`, one result per player
+#[tauri::command]
+fn search_players_cmd(folder_path: String, query: String) -> Result<Vec<PlayerIdentity>, ProcessorError> {
+    json_processor::search_player_identities(&folder_path, &query)
+}
+
+/// Reread one match file after it changed on disk and return its fresh summary
+#[tauri::command]
+fn refresh_match(folder_path: String, match_id: String) -> Result<MatchSummary, ProcessorError> {
+    json_processor::refresh_match(&folder_path, &match_id)
+}
+
+/// Get the source JSON of a match for debugging
+#[tauri::command]
+fn get_raw_match_json(folder_path: String, match_id: String) -> Result<String, ProcessorError> {
+    json_processor::get_raw_match_json(&folder_path, &match_id)
+}
+
+/// List every player in the loaded matches for a global player picker
+#[tauri::command]
+fn list_all_players(folder_path: String) -> Result<Vec<PlayerListing>, ProcessorError> {
+    json_processor::list_all_players(&folder_path)
+}
+
+/// List every loaded match a player appears in, newest first
+#[tauri::command]
+fn get_player_matches(folder_path: String, puuid: String) -> Result<Vec<MatchSummary>, ProcessorError> {
+    json_processor::matches_for_player(&folder_path, &puuid)
+}
+
+/// Get kill counts per weapon across the given matches
+#[tauri::command]
+fn get_weapon_stats(folder_path: String, match_ids: Vec<String>) -> Result<Vec<WeaponStat>, ProcessorError> {
+    json_processor::weapon_stats(&folder_path, &match_ids)
+}
+
+/// Get the kills from one round of a match
+#[tauri::command]
+fn get_round_kills(folder_path: String, match_id: String, round_num: i32) -> Result<Vec<KillEvent>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(json_processor::round_kills(&detail.kill_events, round_num))
+}
+
+/// Get the winner of every round in order, for drawing a round-by-round win bar
+#[tauri::command]
+fn get_round_sequence(folder_path: String, match_id: String) -> Result<Vec<RoundOutcome>, ProcessorError> {
+    let mut rounds = json_processor::get_match_by_id(Some(&folder_path), &match_id)?.rounds;
+    rounds.sort_by_key(|r| r.round_num);
+    Ok(rounds)
+}
+
+/// Get each team's longest win streak and the streak running at the end of a match
+#[tauri::command]
+fn get_match_momentum(folder_path: String, match_id: String) -> Result<MatchMomentum, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::match_momentum(&detail.rounds))
+}
+
+/// Get kills per weapon for each team in a match
+#[tauri::command]
+fn get_weapon_usage(folder_path: String, match_id: String) -> Result<WeaponUsageReport, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(WeaponUsageReport {
+        by_team: json_processor::weapon_usage_by_team(&detail.kill_events, &detail.players),
+        most_used: json_processor::most_used_weapon_per_team(&detail.kill_events, &detail.players),
+    })
+}
+
+/// Get a match's kills binned by round time
+#[tauri::command]
+fn get_kill_timeline(folder_path: String, match_id: String, bucket_millis: i32) -> Result<Vec<TimelineBucket>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    json_processor::kill_timeline(&detail.kill_events, bucket_millis)
+}
+
+/// Get a match's kill density over the full length of the game
+#[tauri::command]
+fn get_match_kill_timeline(folder_path: String, match_id: String, bucket_size_millis: u64) -> Result<KillTimeline, ProcessorError> {
+    if bucket_size_millis == 0 {
+        return Err(ProcessorError::Other("Bucket size must be positive".to_string()));
+    }
+
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(json_processor::compute_kill_timeline(
+        &detail.kill_events,
+        detail.game_length_millis.unwrap_or(0),
+        bucket_size_millis,
+    ))
+}
+
+/// Get kills in a match that traded a teammate's death within the window
+#[tauri::command]
+fn get_trades(folder_path: String, match_id: String, window_millis: i32) -> Result<Vec<TradeKill>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(json_processor::detect_trades(&detail.kill_events, &detail.players, window_millis))
+}
+
+/// Get 1vN clutch situations in a match, won or lost
+#[tauri::command]
+fn get_clutch_situations(folder_path: String, match_id: String) -> Result<Vec<ClutchEvent>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(json_processor::detect_clutches(&detail))
+}
+
+/// Get the 1vX rounds each player won by making the final kill
+#[tauri::command]
+fn get_clutches(folder_path: String, match_id: String) -> Result<Vec<Clutch>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::won_clutches(&detail))
+}
+
+/// Get a match's players ranked by KDA ratio
+#[tauri::command]
+fn get_kda_leaderboard(folder_path: String, match_id: String) -> Result<Vec<PlayerKda>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(json_processor::kda_leaderboard(&detail.players))
+}
+
+/// Aggregate a user-defined series of matches
+#[tauri::command]
+fn get_series_stats(folder_path: String, match_ids: Vec<String>, series_id: String) -> Result<SeriesStats, ProcessorError> {
+    let details = json_processor::get_multiple_match_details(Some(&folder_path), &match_ids)?;
+    Ok(SeriesStats {
+        series_id,
+        ..json_processor::aggregate_series(&details, &match_ids)
+    })
+}
+
+/// Agent pick and win rates across every match in a folder
+#[tauri::command]
+fn get_agent_statistics(folder_path: String) -> Result<Vec<AgentStats>, ProcessorError> {
+    // Loads the folder only if it isn't already, leaving other loaded folders alone
+    let summaries = json_processor::folder_summaries(&folder_path)?;
+    let match_ids: Vec<String> = summaries.into_iter().map(|s| s.match_id).collect();
+    let details = json_processor::get_multiple_match_details(Some(&folder_path), &match_ids)?;
+    Ok(analytics::compute_agent_stats(&details))
+}
+
+/// Cluster a match's kill locations into `k` hot zones
+#[tauri::command]
+fn cluster_kill_positions(folder_path: String, match_id: String, k: u32) -> Result<Vec<KillCluster>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::cluster_kill_locations(&detail.map_display, &detail.kill_events, k, analytics::KMEANS_MAX_ITER))
+}
+
+/// Find dense kill areas and outlying kills in a match with DBSCAN
+#[tauri::command]
+fn dbscan_kill_positions(folder_path: String, match_id: String, epsilon: f64, min_points: usize) -> Result<Vec<SpatialCluster>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::dbscan_kill_locations(&detail.map_display, &detail.kill_events, epsilon, min_points))
+}
+
+/// Get killer-to-victim lines for a match in minimap coordinates
+#[tauri::command]
+fn get_kill_trajectories(folder_path: String, match_id: String) -> Result<Vec<TrajectoryLine>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::kill_trajectories(&detail.kill_events, &detail.map_display))
+}
+
+/// Get how far from their victims a weapon's kills were made in a match
+#[tauri::command]
+fn get_kill_distance_distribution(
+    folder_path: String,
+    match_id: String,
+    weapon: String,
+    bucket_count: u32
+) -> Result<DistributionHistogram, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::kill_distance_distribution(&detail.kill_events, &detail.map_display, &weapon, bucket_count))
+}
+
+/// Get a histogram of a match's kill distances in game units
+#[tauri::command]
+fn get_distance_histogram(folder_path: String, match_id: String, bucket: f64) -> Result<Vec<(f64, u32)>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::distance_histogram(&detail.kill_events, bucket))
+}
+
+/// Get a match's players and kill relationships as a graph
+#[tauri::command]
+fn get_kill_graph(folder_path: String, match_id: String) -> Result<KillGraph, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::match_kill_graph(&detail))
+}
+
+/// Write a match's kill graph to a Graphviz DOT file
+#[tauri::command]
+fn export_kill_graph(folder_path: String, match_id: String, out_path: String) -> Result<(), ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    let mut dot = Vec::new();
+    export::export_kill_graph_dot(&analytics::match_kill_graph(&detail), &mut dot)?;
+    Ok(std::fs::write(&out_path, dot)?)
+}
+
+/// Count kills between two players across every loaded match they both played
+#[tauri::command]
+fn head_to_head(folder_path: String, puuid_a: String, puuid_b: String) -> Result<HeadToHead, ProcessorError> {
+    analytics::head_to_head(&folder_path, &puuid_a, &puuid_b)
+}
+
+/// Picks and total kills and deaths per agent across the given matches
+#[tauri::command]
+fn get_agent_stats(folder_path: String, match_ids: Vec<String>) -> Result<Vec<AgentStat>, ProcessorError> {
+    analytics::agent_stats(&folder_path, &match_ids)
+}
+
+/// Get the highest-kill player of each match for a leaderboard
+#[tauri::command]
+fn top_fraggers(folder_path: String, match_ids: Vec<String>) -> Result<Vec<(String, String, i32)>, ProcessorError> {
+    analytics::top_fraggers(&folder_path, &match_ids)
+}
+
+/// Get every kill, plant, defuse and round boundary of a match in time order
+#[tauri::command]
+fn get_replay_stream(folder_path: String, match_id: String) -> Result<Vec<ReplayEvent>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::build_replay_stream(&detail))
+}
+
+/// Write a match's kill events to a Parquet file
+#[tauri::command]
+fn export_parquet(folder_path: String, match_id: String, output_path: String) -> Result<(), ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    export::export_kill_events_to_parquet(&detail.kill_events, &detail.map_display, Path::new(&output_path))
+}
+
+/// Save a match in the app's normalized JSON schema
+#[tauri::command]
+fn export_normalized_match(folder_path: String, match_id: String, output_path: String) -> Result<(), ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    let mut json = Vec::new();
+    export::export_normalized_json(&detail, &mut json)?;
+    Ok(std::fs::write(&output_path, json)?)
+}
+
+/// Open a match saved by `export_normalized_match` without loading its folder
+#[tauri::command]
+fn load_normalized_match(path: String) -> Result<MatchDetail, ProcessorError> {
+    json_processor::load_normalized_json(Path::new(&path))
+}
+
+/// Get a map's coordinate transform and reference minimap image size and asset
+#[tauri::command]
+fn get_map_calibration(map_display: String) -> Result<MapCalibration, ProcessorError> {
+    coordinates::map_calibration(&map_display).ok_or_else(|| ProcessorError::Other(format!("Unknown map: {}", map_display)))
+}
+
+/// Get each player's average kill and death position on the minimap
+#[tauri::command]
+fn get_player_centroids(folder_path: String, match_id: String) -> Result<Vec<PlayerCentroid>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::all_player_centroids(&detail.kill_events, &detail.players, &detail.map_display))
+}
+
+/// Get every kill with the minimap positions of all alive players at that moment
+#[tauri::command]
+fn get_kill_snapshots(folder_path: String, match_id: String) -> Result<Vec<KillSnapshot>, ProcessorError> {
+    let rounds = json_processor::get_match_rounds(Some(&folder_path), &match_id)?;
+    Ok(analytics::extract_kill_snapshots(&rounds.round_results, &rounds.map_display))
+}
+
+/// Get the extent of a match's kill positions for calibration-free normalization
+#[tauri::command]
+fn get_kill_bounds(folder_path: String, match_id: String) -> Result<Bounds, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    Ok(analytics::compute_bounds(&detail.kill_events))
+}
+
+/// Count a match's kills per map zone (site, mid, ...)
+#[tauri::command]
+fn get_zone_counts(folder_path: String, match_id: String, perspective: Perspective) -> Result<HashMap<String, u32>, ProcessorError> {
+    let rounds = json_processor::get_match_rounds(Some(&folder_path), &match_id)?;
+    let zones = zones::bundled_map_zones(&rounds.map_display)?
+        .ok_or_else(|| ProcessorError::Other(format!("No zones defined for {}", rounds.map_display)))?;
+    Ok(zones::classify_kill_zones(&rounds.round_results, &zones, perspective))
+}
+
+/// Bin a match's kills onto a grid and flatten it to one layer, blended by default
+#[tauri::command]
+fn compute_match_heatmap(
+    folder_path: String,
+    match_id: String,
+    grid_width: u32,
+    grid_height: u32,
+    blend_mode: Option<BlendMode>
+) -> Result<BlendedHeatmap, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    let heatmap = heatmap::compute_heatmap(&detail.map_display, &detail.kill_events, grid_width, grid_height);
+    Ok(heatmap::blend_heatmap_mode(&heatmap, blend_mode.unwrap_or_default()))
+}
+
+/// Show where a match's kills moved between the first and second half
+#[tauri::command]
+fn get_half_diff_heatmap(folder_path: String, match_id: String, grid_width: u32, grid_height: u32) -> Result<DiffHeatmap, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    let half_heatmap = |phase| {
+        let events: Vec<KillEvent> = detail.kill_events
+            .iter()
+            .filter(|e| e.round_phase == phase)
+            .cloned()
+            .collect();
+        heatmap::compute_heatmap(&detail.map_display, &events, grid_width, grid_height)
+    };
+
+    Ok(heatmap::diff_heatmaps(
+        &half_heatmap(RoundPhase::FirstHalf),
+        &half_heatmap(RoundPhase::SecondHalf),
+    ))
+}
+
+/// Find the grid cells where the most players died in a match
+#[tauri::command]
+fn get_top_danger_positions(
+    folder_path: String,
+    match_id: String,
+    n: usize,
+    grid_width: u32,
+    grid_height: u32
+) -> Result<Vec<DangerPosition>, ProcessorError> {
+    let detail = json_processor::get_match_by_id(Some(&folder_path), &match_id)?;
+    let heatmap = heatmap::compute_heatmap(&detail.map_display, &detail.kill_events, grid_width, grid_height);
+    Ok(heatmap::top_danger_positions(&heatmap, n))
+}
+
+/// Aggregate killer positions across matches and save them as one heatmap PNG
+#[tauri::command]
+fn export_heatmap_png(folder_path: String, match_ids: Vec<String>, grid_size: u32, out_path: String) -> Result<(), ProcessorError> {
+    if grid_size == 0 {
+        return Err(ProcessorError::Other("Grid size must be at least 1".to_string()));
+    }
+
+    let details = json_processor::get_multiple_match_details(Some(&folder_path), &match_ids)?;
+    let cells = heatmap::aggregate_kill_cells(&details, grid_size, WeightMode::Uniform, GridFlip::default());
+    export::render_heatmap_png(&cells, grid_size, &out_path)
+}
+
+/// Combine killer positions across matches into one grid, weighting each kill by `weight_mode`
+#[tauri::command]
+fn aggregate_heatmap(
+    folder_path: String,
+    match_ids: Vec<String>,
+    grid_size: u32,
+    weight_mode: Option<WeightMode>,
+    flip: Option<GridFlip>
+) -> Result<Vec<GridCell>, ProcessorError> {
+    let details = json_processor::get_multiple_match_details(Some(&folder_path), &match_ids)?;
+    Ok(heatmap::aggregate_kill_cells(&details, grid_size, weight_mode.unwrap_or_default(), flip.unwrap_or_default()))
+}
+
+/// Bin every alive player's position at kill time across matches, optionally for one player
+#[tauri::command]
+fn aggregate_positions(
+    folder_path: String,
+    match_ids: Vec<String>,
+    puuid: Option<String>,
+    grid_size: u32,
+    flip: Option<GridFlip>
+) -> Result<Vec<GridCell>, ProcessorError> {
+    let matches = match_ids
+        .iter()
+        .map(|match_id| json_processor::get_match_rounds(Some(&folder_path), match_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(heatmap::aggregate_player_positions(&matches, puuid.as_deref(), grid_size, flip.unwrap_or_default()))
+}
+
+/// The preferred heatmap colormap, falling back to the default for unknown names
+fn prefs_colormap(prefs: &UserPrefs) -> Colormap {
+    serde_json::from_value(serde_json::Value::String(prefs.default_colormap.clone()))
+        .unwrap_or_default()
+}
+
+/// Save a match's killer heatmap as an SVG, optionally over a minimap image
+///
+/// Grid size and colormap come from the saved preferences.
+#[tauri::command]
+fn export_heatmap_svg(
+    app: tauri::AppHandle,
+    detail: MatchDetail,
+    background_path: Option<String>,
+    output_path: String
+) -> Result<(), ProcessorError> {
+    let prefs = app_state::load_prefs(&app);
+    let heatmap = heatmap::compute_heatmap(
+        &detail.map_display,
+        &detail.kill_events,
+        prefs.default_grid_width,
+        prefs.default_grid_height,
+    );
+
+    export::render_heatmap_to_svg(
+        &heatmap,
+        background_path.as_deref().map(Path::new),
+        prefs_colormap(&prefs),
+        Path::new(&output_path),
+    )
+}
+
+/// Render a heatmap PNG for every match in a folder, emitting "batch-export-progress" events
+#[tauri::command]
+fn batch_export_heatmaps(
+    app: tauri::AppHandle,
+    folder_path: String,
+    output_dir: String,
+    grid_width: u32,
+    grid_height: u32
+) -> Result<BatchExportResult, ProcessorError> {
+    let summaries = json_processor::folder_summaries(&folder_path)?;
+    let colormap = prefs_colormap(&app_state::load_prefs(&app));
+
+    export::export_all_heatmaps(
+        &summaries,
+        &folder_path,
+        Path::new(&output_dir),
+        grid_width,
+        grid_height,
+        colormap,
+        |processed, total| {
+            if let Err(e) = app.emit("batch-export-progress", (processed, total)) {
+                eprintln!("Failed to emit batch-export-progress event: {}", e);
+            }
+        },
+    )
+}
+
+/// Compare two matches by the players they have in common
+#[tauri::command]
+fn compare_matches(folder_path: String, match_id_a: String, match_id_b: String) -> Result<MatchComparison, ProcessorError> {
+    let a = json_processor::get_match_by_id(Some(&folder_path), &match_id_a)?;
+    let b = json_processor::get_match_by_id(Some(&folder_path), &match_id_b)?;
+    Ok(json_processor::compare_matches(&a, &b))
+}
+
+/// Drop cached match details so they are reread from disk
+#[tauri::command]
+fn clear_match_cache() {
+    json_processor::clear_match_cache();
+}
+
+/// Start watching a folder for newly added match files
+#[tauri::command]
+fn start_folder_watch(app: tauri::AppHandle, folder_path: String) -> Result<(), ProcessorError> {
+    watcher::watch_folder(&folder_path, app)
+}
+
+/// Stop watching the current folder
+#[tauri::command]
+fn stop_folder_watch() {
+    watcher::unwatch_folder();
+}
+
+/// Remember the last folder and filters for the next launch
+#[tauri::command]
+fn save_app_state(app: tauri::AppHandle, state: AppState) -> Result<(), ProcessorError> {
+    let config_dir = app.path()
+        .app_config_dir()
+        .map_err(|e| ProcessorError::Other(format!("Failed to resolve config directory: {}", e)))?;
+    app_state::save_app_state(&state, &config_dir)
+}
+
+/// Restore the state saved by the previous launch
+#[tauri::command]
+fn load_app_state(app: tauri::AppHandle) -> AppState {
+    match app.path().app_config_dir() {
+        Ok(config_dir) => app_state::load_app_state(&config_dir),
+        Err(_) => AppState::default(),
+    }
+}
+
+/// Format a duration in milliseconds as "42m 17s"
+#[tauri::command]
+fn format_duration(millis: i64) -> String {
+    utils::format_game_duration(millis)
+}
+
+/// Get the saved heatmap defaults and last folder
+#[tauri::command]
+fn get_prefs(app: tauri::AppHandle) -> UserPrefs {
+    app_state::load_prefs(&app)
+}
+
+/// Persist heatmap defaults and last folder
+#[tauri::command]
+fn set_prefs(app: tauri::AppHandle, prefs: UserPrefs) -> Result<(), ProcessorError> {
+    app_state::save_prefs(&app, &prefs)?;
+    json_processor::set_max_coordinate_magnitude(prefs.max_coordinate_magnitude);
+    Ok(())
+}
+
+/// Add a label to a match
+#[tauri::command]
+fn add_match_tag(app: tauri::AppHandle, match_id: String, label: String) -> Result<(), ProcessorError> {
+    app_state::tag_store(&app)?.add_tag(&match_id, &label)
+}
+
+/// Remove a label from a match
+#[tauri::command]
+fn remove_match_tag(app: tauri::AppHandle, match_id: String, label: String) -> Result<(), ProcessorError> {
+    app_state::tag_store(&app)?.remove_tag(&match_id, &label)
+}
+
+/// Get the labels on a match
+#[tauri::command]
+fn get_match_tags(app: tauri::AppHandle, match_id: String) -> Result<Vec<String>, ProcessorError> {
+    Ok(app_state::tag_store(&app)?.get_tags(&match_id))
+}
+
+/// List the loaded matches carrying a label
+#[tauri::command]
+fn filter_matches_by_tag(app: tauri::AppHandle, label: String) -> Result<Vec<MatchSummary>, ProcessorError> {
+    let summaries = json_processor::loaded_summaries();
+    Ok(app_state::tag_store(&app)?.filter_by_tag(&summaries, &label))
 }
 
 /// Save file using native file picker
 #[tauri::command]
-async fn save_file(app: tauri::AppHandle, extensions: Vec<String>, default_name: Option<String>) -> Result<Option<String>, String> {
+async fn save_file(app: tauri::AppHandle, extensions: Vec<String>, default_name: Option<String>) -> Result<Option<String>, ProcessorError> {
     use tauri_plugin_dialog::DialogExt;
 
     let mut dialog = app.dialog().file();
@@ -85,12 +836,12 @@ async fn save_file(app: tauri::AppHandle, extensions: Vec<String>, default_name:
 
 /// Write binary data to file
 #[tauri::command]
-async fn write_binary_file(path: String, contents: Vec<u8>) -> Result<(), String> {
+async fn write_binary_file(path: String, contents: Vec<u8>) -> Result<(), ProcessorError> {
     use std::fs;
     use std::path::Path;
 
     fs::write(Path::new(&path), contents)
-        .map_err(|e| format!("Failed to write file: {}", e))
+        .map_err(|e| ProcessorError::Other(format!("Failed to write file: {}", e)))
 }
 
 fn main() {
@@ -100,15 +851,95 @@ fn main() {
         .plugin(tauri_plugin_shell::init())
         .invoke_handler(tauri::generate_handler![
             select_folder,
+            select_zip_file,
+            validate_folder,
             load_matches,
+            load_matches_async,
+            load_matches_page,
             load_matches_with_progress,
+            load_matches_multi,
+            load_matches_from_zip,
+            load_matches_filtered,
+            get_facets_cmd,
+            rebuild_index,
             get_match_detail,
+            get_raw_match_json,
+            refresh_match,
             get_multiple_match_details,
+            get_multiple_match_details_tuned,
             get_multiple_match_details_with_progress,
+            stream_match_details,
+            search_players,
+            search_players_cmd,
+            get_player_matches,
+            list_all_players,
+            get_weapon_stats,
+            get_weapon_usage,
+            get_round_kills,
+            get_round_sequence,
+            get_match_momentum,
+            get_kill_timeline,
+            get_match_kill_timeline,
+            get_trades,
+            get_clutch_situations,
+            get_clutches,
+            compare_matches,
+            compute_match_heatmap,
+            get_half_diff_heatmap,
+            get_top_danger_positions,
+            export_heatmap_png,
+            aggregate_heatmap,
+            aggregate_positions,
+            batch_export_heatmaps,
+            export_heatmap_svg,
+            get_kda_leaderboard,
+            get_series_stats,
+            get_agent_statistics,
+            cluster_kill_positions,
+            dbscan_kill_positions,
+            get_kill_trajectories,
+            get_kill_distance_distribution,
+            get_distance_histogram,
+            get_kill_graph,
+            export_kill_graph,
+            export_parquet,
+            export_normalized_match,
+            load_normalized_match,
+            top_fraggers,
+            get_agent_stats,
+            head_to_head,
+            get_replay_stream,
+            get_map_calibration,
+            get_player_centroids,
+            get_kill_snapshots,
+            get_kill_bounds,
+            get_zone_counts,
+            clear_match_cache,
+            start_folder_watch,
+            stop_folder_watch,
+            save_app_state,
+            load_app_state,
+            get_prefs,
+            format_duration,
+            set_prefs,
+            add_match_tag,
+            remove_match_tag,
+            get_match_tags,
+            filter_matches_by_tag,
             save_file,
             write_binary_file
         ])
         .setup(|app| {
+            let handle = app.handle().clone();
+            json_processor::set_max_coordinate_magnitude(app_state::load_prefs(&handle).max_coordinate_magnitude);
+
+            // Let the UI offer a re-index when loaded matches have moved on disk
+            json_processor::set_stale_index_listener(move |entries| {
+                if let Err(e) = handle.emit("index-stale", entries) {
+                    eprintln!("Failed to emit index-stale event: {}", e);
+                }
+            });
+
             // Ensure a window is created if none exists
             let window = app.get_webview_window("main");
             if window.is_none() {
@@ -128,4 +959,35 @@ fn main() {
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn background_load_resolves_and_reports_progress() {
+        let _guard = lock_global_state();
+        let dir = tempfile::tempdir().unwrap();
+        write_match(dir.path(), "m1.json", &simple_match("m1"));
+        write_match(dir.path(), "m2.json", &simple_match("m2"));
+        let reports = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&reports);
+
+        let result = tauri::async_runtime::block_on(load_folder_in_background(
+            path_str(dir.path()).to_string(),
+            None,
+            LoadOptions::default(),
+            move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(result.summaries.len(), 2);
+        assert!(reports.load(Ordering::SeqCst) > 0);
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Location coordinates on the map
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,10 +15,210 @@ pub struct Location {
 pub struct MatchSummary {
     pub match_id: String,
     pub map: String,
+    /// Human-readable map name, e.g. "Split" for `/Game/Maps/Bonsai/Bonsai`
+    pub map_display: String,
     pub region: String,
-    pub game_start: DateTime<Utc>,
+    /// None when the export's start time is missing or out of range
+    pub game_start: Option<DateTime<Utc>>,
+    pub game_start_valid: bool,
     pub teams: Vec<String>,
+    /// Non-observer player count per team, e.g. {"Blue": 5, "Red": 5}
+    pub team_sizes: HashMap<String, u32>,
     pub score: String,
+    /// Rounds won by Blue or Red, i.e. the rounds that make up `score`
+    pub rounds_counted: i32,
+    pub game_length_millis: Option<i64>,
+    /// Game length as "42m 17s", or None when the export omits it
+    pub game_length_display: Option<String>,
+    /// Queue ID from the export, e.g. "competitive"; None when the file doesn't say
+    pub queue: Option<String>,
+    /// Client build the match was played on, e.g. "release-08.11-shipping-6-2383403"
+    pub game_version: Option<String>,
+    /// False when no player is on Blue or Red (observer-only records)
+    pub is_valid_match: bool,
+}
+
+/// Criteria for narrowing a list of match summaries; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchFilter {
+    pub maps: Option<Vec<String>>,
+    pub regions: Option<Vec<String>>,
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+    /// Minimum total rounds, taken from the summary score
+    pub min_rounds: Option<i32>,
+    /// Queue IDs to keep; matches with no recorded queue always pass
+    pub queues: Option<Vec<String>>,
+    pub sort: Option<SortOrder>,
+}
+
+/// Ordering applied to match summary lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    GameStartAsc,
+    #[default]
+    GameStartDesc,
+    MapAsc,
+    RegionAsc,
+    /// Shortest matches first, by total rounds in the score
+    ScoreAsc,
+}
+
+/// Distinct values present in a set of matches, for populating filter controls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Facets {
+    /// Display names, sorted and deduplicated
+    pub maps: Vec<String>,
+    pub regions: Vec<String>,
+    pub date_min: Option<DateTime<Utc>>,
+    pub date_max: Option<DateTime<Utc>>,
+}
+
+/// Tuning knobs for loading a folder of match files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadOptions {
+    /// Memory-map files instead of streaming them; ignored unless built with the `mmap` feature
+    pub use_mmap: bool,
+    /// Minimum number of files handed to each worker at once
+    pub batch_size: usize,
+    pub parallel: bool,
+    /// Descend into symlinked files and folders; off by default so links can't pull in unrelated trees
+    pub follow_symlinks: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            use_mmap: false,
+            batch_size: 10,
+            parallel: true,
+            follow_symlinks: false,
+        }
+    }
+}
+
+/// One page of match summaries out of everything loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchPage {
+    pub summaries: Vec<MatchSummary>,
+    /// Number of matches across all pages
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// Outcome of loading a folder of match files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadResult {
+    pub summaries: Vec<MatchSummary>,
+    pub duplicates_skipped: usize,
+    /// Files that could not be read or parsed as match data
+    pub parse_errors: usize,
+    /// Problems with matches that still loaded, such as an unsupported game version
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// True when the folders held no loadable matches, e.g. an empty or JSON-free directory
+    #[serde(default)]
+    pub no_matches_found: bool,
+}
+
+/// Result of checking a folder for loadable match files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderValidation {
+    pub total_files: usize,
+    pub valid_vct_files: usize,
+    /// The first few parse errors, for showing the user why files were rejected
+    pub sample_errors: Vec<String>,
+}
+
+/// UI state remembered between launches
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppState {
+    #[serde(default)]
+    pub last_folder: Option<String>,
+    /// Filter settings as the frontend stores them
+    #[serde(default)]
+    pub last_filters: Option<serde_json::Value>,
+}
+
+/// User-defined labels on a match, e.g. "reviewed" or a tournament name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchTag {
+    pub match_id: String,
+    pub labels: Vec<String>,
+}
+
+/// Heatmap defaults and the last folder, kept in the app data directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserPrefs {
+    pub last_folder: Option<String>,
+    pub default_grid_width: u32,
+    pub default_grid_height: u32,
+    pub default_colormap: String,
+    /// Smoothing radius, same scale as the heatmap's density slider
+    pub default_sigma: f32,
+    /// Kills with a raw x or y coordinate beyond this magnitude are dropped as corrupt
+    pub max_coordinate_magnitude: i32,
+}
+
+impl Default for UserPrefs {
+    fn default() -> Self {
+        UserPrefs {
+            last_folder: None,
+            default_grid_width: 1024,
+            default_grid_height: 1024,
+            default_colormap: "viridis".to_string(),
+            default_sigma: 5.0,
+            max_coordinate_magnitude: crate::json_processor::DEFAULT_MAX_COORDINATE_MAGNITUDE,
+        }
+    }
+}
+
+/// Identity of a player appearing in a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRef {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+/// A match summary together with its roster, as kept by the load pass and summary cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedSummary {
+    pub summary: MatchSummary,
+    pub roster: Vec<PlayerRef>,
+}
+
+/// A player in the loaded dataset, with the number of matches they appear in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerListing {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub match_count: u32,
+}
+
+/// A distinct player matched by `name#tag` search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerIdentity {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    /// Loaded matches the player appears in
+    pub match_count: u32,
+}
+
+/// A player matched by name search, with how often they appear in the loaded matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSearchResult {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub matches_found: u32,
+    pub most_recent_match_id: String,
 }
 
 /// Player statistics in a match
@@ -37,16 +239,491 @@ pub struct PlayerStats {
     pub is_observer: bool,
 }
 
+/// Part of the match a round belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundPhase {
+    FirstHalf,
+    SecondHalf,
+    Overtime,
+}
+
+/// How a round played out around the spike
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundType {
+    /// Attackers planted and won the round
+    Execute,
+    /// The spike was planted but the defenders won the round
+    Retake,
+    /// The spike was never planted
+    NoPlant,
+}
+
+/// Source of the damage that finished a kill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KillCategory {
+    Gun,
+    Ability,
+    Melee,
+    Bomb,
+    Fall,
+    #[default]
+    Unknown,
+}
+
 /// Kill event with positions for heatmap visualization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KillEvent {
     pub killer_puuid: String,
     pub victim_puuid: String,
     pub weapon: Option<String>,
-    pub killer_location: Location,
+    #[serde(default)]
+    pub kill_category: KillCategory,
+    /// None when the killer is missing from the export's player locations
+    pub killer_location: Option<Location>,
     pub victim_location: Location,
+    /// Straight-line killer-to-victim distance in game units; 0 when the killer's position is unknown
+    #[serde(default)]
+    pub distance: f64,
+    /// False when `killer_location` is unknown, so `distance` is meaningless
+    #[serde(default)]
+    pub distance_reliable: bool,
+    /// PUUIDs of the players credited with an assist
+    #[serde(default)]
+    pub assistants: Vec<String>,
+    /// Whether the killer's team won the round; None when the killer's team or the winner is unknown
+    #[serde(default)]
+    pub round_won_by_killer: Option<bool>,
     pub round_num: i32,
+    pub round_phase: RoundPhase,
     pub round_time_millis: i32,
+    /// Time since game start, when the export includes it
+    #[serde(default)]
+    pub game_time_millis: Option<i64>,
+}
+
+/// Kills per weapon for each team in a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponUsageReport {
+    /// Team → weapon → kill count
+    pub by_team: HashMap<String, HashMap<String, u32>>,
+    /// Team → the weapon it got the most kills with
+    pub most_used: HashMap<String, String>,
+}
+
+/// A player's minimap position at the moment of a kill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerPosition {
+    pub puuid: String,
+    pub uv_x: f64,
+    pub uv_y: f64,
+}
+
+/// A kill together with where every alive player stood when it happened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillSnapshot {
+    pub event: KillEvent,
+    pub all_positions: Vec<PlayerPosition>,
+}
+
+/// Kill count for a single weapon across one or more matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponStat {
+    pub weapon: String,
+    pub kills: u32,
+}
+
+/// Number of kills within one slice of round time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    pub bucket_start_millis: i32,
+    pub count: u32,
+}
+
+/// A kill that avenged a teammate's death shortly before
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeKill {
+    /// The avenging kill
+    pub trade: KillEvent,
+    /// The earlier kill of a teammate by the player who was then traded
+    pub traded: KillEvent,
+}
+
+/// Kills and deaths of one player in each of two matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdaDiff {
+    pub kills_a: i32,
+    pub kills_b: i32,
+    pub deaths_a: i32,
+    pub deaths_b: i32,
+}
+
+/// Players shared between two matches and how their stats differ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchComparison {
+    pub shared_puuids: Vec<String>,
+    pub match_a_id: String,
+    pub match_b_id: String,
+    /// Keyed by PUUID, one entry per shared player
+    pub player_kda_diff: HashMap<String, KdaDiff>,
+    /// Rounds won by Blue in match A and match B
+    pub blue_rounds: (u32, u32),
+    /// Rounds won by Red in match A and match B
+    pub red_rounds: (u32, u32),
+    /// Kills in match A and match B
+    pub total_kills: (u32, u32),
+    /// Match B's kills minus match A's, per weapon
+    pub weapon_kill_deltas: HashMap<String, i32>,
+}
+
+/// Totals for a group of matches played as one series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesStats {
+    pub series_id: String,
+    pub match_ids: Vec<String>,
+    pub total_rounds: u32,
+    pub maps_played: Vec<String>,
+    /// Maps won per team ID
+    pub team_scores: HashMap<String, u32>,
+}
+
+/// Which end of a kill to place on the map, matching the heatmap's kills/deaths modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Perspective {
+    /// Killer positions
+    Kills,
+    /// Victim positions
+    Deaths,
+    /// Positions of the players who assisted each kill
+    Assists,
+}
+
+/// Named area of a map, such as a site or mid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapZone {
+    pub name: String,
+    /// Polygon vertices in raw game coordinates
+    pub polygon: Vec<Location>,
+}
+
+/// Every zone defined for one map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapZones {
+    pub map: String,
+    pub zones: Vec<MapZone>,
+}
+
+/// Axis-aligned rectangle in raw game coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+/// Killer and victim positions binned onto a grid over the minimap
+///
+/// Counts are row-major from the top-left cell, `grid_width * grid_height` long.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillHeatmap {
+    pub grid_width: u32,
+    pub grid_height: u32,
+    pub killer_counts: Vec<u32>,
+    pub victim_counts: Vec<u32>,
+}
+
+/// Which positions a single-layer heatmap shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlendMode {
+    Killer,
+    Victim,
+    /// Killer and victim grids weighted equally
+    #[default]
+    Blended,
+}
+
+/// A single heatmap layer with intensities between 0 and 1, row-major like [`KillHeatmap`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlendedHeatmap {
+    pub grid_width: u32,
+    pub grid_height: u32,
+    pub intensities: Vec<f32>,
+}
+
+/// Per-cell change in kills between two heatmaps, split by direction
+///
+/// Both grids are row-major and hold the size of the change, so every value is
+/// zero or positive and a cell is non-zero in at most one of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHeatmap {
+    pub grid_width: u32,
+    pub grid_height: u32,
+    /// Cells with more kills in the second heatmap
+    pub positive: Vec<f32>,
+    /// Cells with fewer kills in the second heatmap
+    pub negative: Vec<f32>,
+}
+
+/// A heatmap cell where many players died, for labeling hot corners on the map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DangerPosition {
+    /// Center of the cell in minimap space
+    pub uv_x: f64,
+    pub uv_y: f64,
+    pub victim_count: u32,
+    /// Row-major index into the heatmap grid
+    pub cell_index: u32,
+}
+
+/// One non-empty heatmap cell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridCell {
+    pub x: u32,
+    pub y: u32,
+    /// Summed contribution of the positions in this cell; a plain count unless weighted
+    pub weight: f64,
+}
+
+/// Mirroring applied to minimap positions before binning, for minimap images drawn the other way round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridFlip {
+    /// Mirror left to right, so column `c` becomes `width - 1 - c`
+    pub flip_x: bool,
+    /// Mirror top to bottom, so row `r` becomes `height - 1 - r`
+    pub flip_y: bool,
+}
+
+/// How much each kill contributes to a multi-match heatmap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WeightMode {
+    /// Every kill counts once
+    #[default]
+    Uniform,
+    /// Kills scaled by how recent their match is, from 1/n for the oldest to 1 for the newest
+    RecencyLinear,
+    /// Only the first kill of each round counts
+    OpeningOnly,
+}
+
+/// Color scale for rendered heatmaps; names match `UserPrefs::default_colormap`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Colormap {
+    Grayscale,
+    #[default]
+    Viridis,
+    Inferno,
+}
+
+/// Outcome of rendering heatmaps for many matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExportResult {
+    pub exported: usize,
+    pub failed: usize,
+    pub output_paths: Vec<PathBuf>,
+}
+
+/// Outcome of streaming match details to the frontend one at a time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetailStreamResult {
+    pub loaded: usize,
+    /// Matches that couldn't be loaded; the rest were still sent
+    pub failed_match_ids: Vec<String>,
+}
+
+/// Hot zone found by clustering kill locations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillCluster {
+    /// Cluster center in normalized minimap coordinates
+    pub centroid_uv: (f64, f64),
+    pub member_count: u32,
+    /// Most common weapon among the cluster's kills
+    pub representative_weapon: Option<String>,
+}
+
+/// Density-based group of kills, as indices into the clustered kill events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpatialCluster {
+    pub core_members: Vec<usize>,
+    /// Noise kills whose nearest neighbour belongs to this cluster
+    pub outliers: Vec<usize>,
+}
+
+/// Line from killer to victim in normalized minimap coordinates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrajectoryLine {
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+    pub weapon: Option<String>,
+    pub round_num: i32,
+}
+
+/// Counts of values falling between consecutive edges, in minimap units
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionHistogram {
+    /// One more edge than there are counts
+    pub bucket_edges_uv: Vec<f64>,
+    pub counts: Vec<u32>,
+}
+
+/// Kill counts in equal-width buckets spanning a whole match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillTimeline {
+    /// Start time of each bucket since game start
+    pub buckets_millis: Vec<u64>,
+    /// Kills credited to a player (excludes self-inflicted and environmental deaths)
+    pub killer_counts: Vec<u32>,
+    /// All deaths, whatever the cause
+    pub victim_counts: Vec<u32>,
+}
+
+/// Which team won a round, in match order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundOutcome {
+    pub round_num: i32,
+    pub winning_team: Option<String>,
+    pub round_phase: RoundPhase,
+    /// None when the spike wasn't planted this round
+    #[serde(default)]
+    pub plant: Option<SpikeEvent>,
+    /// None when the spike wasn't defused this round
+    #[serde(default)]
+    pub defuse: Option<SpikeEvent>,
+}
+
+/// How round wins ran together over a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchMomentum {
+    /// Longest run of consecutive round wins per team
+    pub max_streaks: HashMap<String, u32>,
+    /// Team on a streak when the match ended, or "Unknown" when the last round has no winner
+    pub current_streak_team: String,
+    pub current_streak_length: u32,
+}
+
+/// A spike plant or defuse within a round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpikeEvent {
+    pub player_puuid: String,
+    pub round_time_millis: i32,
+    pub location: Option<Location>,
+    /// Bomb site letter; only recorded for plants
+    pub site: Option<String>,
+}
+
+/// Kind of entry in a match replay stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayEventType {
+    Kill,
+    PlantSpike,
+    DefuseSpike,
+    RoundStart,
+    RoundEnd,
+}
+
+/// One timestamped entry in a match replay stream
+///
+/// `payload` holds the event's details: the full kill event for kills, the
+/// spike event plus `round_num` for plants and defuses, and `round_num` (and
+/// `winning_team` at round end) for round boundaries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub time_since_match_start_millis: i64,
+    pub event_type: ReplayEventType,
+    pub payload: serde_json::Value,
+}
+
+/// A player's KDA ratio for leaderboards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerKda {
+    pub puuid: String,
+    pub game_name: String,
+    pub kda_ratio: f64,
+}
+
+/// Pick and win rates for one agent across a set of matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentStats {
+    pub agent_name: String,
+    pub times_picked: u32,
+    pub wins: u32,
+    pub avg_kills: f64,
+    pub avg_deaths: f64,
+}
+
+/// Pick count and total kills and deaths for one agent across a set of matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentStat {
+    /// Agent display name, or "Unknown" for missing or unrecognized agents
+    pub agent_name: String,
+    pub picks: u32,
+    pub total_kills: i32,
+    pub total_deaths: i32,
+}
+
+/// Who killed whom in a match, for network graph views
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillGraph {
+    pub nodes: Vec<PlayerNode>,
+    pub edges: Vec<KillEdge>,
+}
+
+/// How often two players killed each other across the matches they both played
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadToHead {
+    pub a_kills_b: u32,
+    pub b_kills_a: u32,
+    /// Matches both players appear in
+    pub matches: u32,
+}
+
+/// Mean minimap position of a player's kills and deaths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerCentroid {
+    pub puuid: String,
+    pub game_name: String,
+    pub team: String,
+    pub uv_x: f64,
+    pub uv_y: f64,
+}
+
+/// A player in a kill graph, with the team used to color them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerNode {
+    pub puuid: String,
+    pub game_name: String,
+    pub team: String,
+}
+
+/// Every kill of one player by another across the match, as a single weighted edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillEdge {
+    pub from_puuid: String,
+    pub to_puuid: String,
+    pub kill_count: u32,
+}
+
+/// A round where one player was left alone against two or more opponents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClutchEvent {
+    pub round_num: i32,
+    pub clutch_player_puuid: String,
+    /// Opponents alive when the player became the last of their team
+    pub opponents_remaining: u32,
+    pub won: bool,
+    /// Whether the clutching player made the last kill of the round
+    pub got_final_kill: bool,
+}
+
+/// A round a player won as the last of their team, closing it out themselves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Clutch {
+    pub puuid: String,
+    pub round_num: i32,
+    /// Opponents alive when the player became the last of their team
+    pub enemies_remaining: u32,
 }
 
 /// Detailed match information
@@ -54,13 +731,94 @@ pub struct KillEvent {
 pub struct MatchDetail {
     pub match_id: String,
     pub map: String,
+    pub map_display: String,
     pub region: String,
-    pub game_start: DateTime<Utc>,
-    pub game_length_millis: i64,
+    /// None when the export's start time is missing or out of range
+    pub game_start: Option<DateTime<Utc>>,
+    pub game_start_valid: bool,
+    pub game_length_millis: Option<i64>,
+    /// Game length as "42m 17s", or "Unknown" when the export omits it
+    pub game_length_formatted: String,
     pub rounds_played: i32,
+    pub overtime_rounds: u32,
     pub winning_team: String,
     pub players: Vec<PlayerStats>,
+    /// Player with the highest combat score
+    pub mvp_puuid: Option<String>,
     pub kill_events: Vec<KillEvent>,
+    /// Kills left out of `kill_events` because their coordinates were implausible
+    #[serde(default)]
+    pub dropped_kill_events: u32,
+    pub rounds: Vec<RoundOutcome>,
+    /// Team ID → organization name, falling back to the ID ("Blue"/"Red") when the export doesn't name the team
+    #[serde(default)]
+    pub team_display_names: HashMap<String, String>,
+    /// Classification of each round, in the same order as `rounds`
+    #[serde(default)]
+    pub round_types: Vec<RoundType>,
+}
+
+/// Minimap positions of one kill, None where the position is unknown or off the map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillUv {
+    pub killer_u: Option<f64>,
+    pub killer_v: Option<f64>,
+    pub victim_u: Option<f64>,
+    pub victim_v: Option<f64>,
+}
+
+/// A match in the app's own schema, with minimap positions already computed
+///
+/// The detail's fields sit at the top level, so the file also reads back as a plain [`MatchDetail`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedMatch {
+    #[serde(flatten)]
+    pub detail: MatchDetail,
+    /// One entry per kill event, in the same order
+    pub kill_uvs: Vec<KillUv>,
+}
+
+/// Game-to-minimap transform and reference minimap image for a map, for placing heatmap overlays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapCalibration {
+    /// Map display name, e.g. "Ascent"
+    pub map: String,
+    pub x_multiplier: f64,
+    pub y_multiplier: f64,
+    pub x_scalar_to_add: f64,
+    pub y_scalar_to_add: f64,
+    pub image_width: u32,
+    pub image_height: u32,
+    /// valorant-api map UUID; the image is `https://media.valorant-api.com/maps/{key}/displayicon.png`
+    pub minimap_asset_key: String,
+}
+
+/// Where a match lives on disk, as recorded in the match index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub path: PathBuf,
+    /// Path relative to the root folder it was indexed under, used to find the file again if the folder moves
+    pub relative_path: PathBuf,
+}
+
+/// Everything a folder load produces, saved so an unchanged folder can be restored without parsing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderIndexCache {
+    /// Match files found in the folder, so added or removed files invalidate the cache
+    pub file_count: usize,
+    pub index: HashMap<String, IndexEntry>,
+    pub result: LoadResult,
+    /// Roster of every kept match, keyed by match ID
+    pub rosters: HashMap<String, Vec<PlayerRef>>,
+}
+
+/// An index entry whose file was no longer at its recorded path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleIndexEntry {
+    pub match_id: String,
+    pub indexed_path: PathBuf,
+    /// Where the file was found again, if it was
+    pub relocated_path: Option<PathBuf>,
 }
 
 /// Raw JSON structures for parsing VCT files
@@ -73,6 +831,25 @@ pub struct VctMatchData {
     pub round_results: Vec<RoundResult>,
 }
 
+/// The parts of a match file needed for a list-view summary
+///
+/// Round results keep only the winner, so serde skips over kills and player
+/// locations instead of allocating them.
+#[derive(Debug, Deserialize)]
+pub struct VctMatchSummaryData {
+    #[serde(rename = "matchInfo")]
+    pub match_info: MatchInfo,
+    pub players: Vec<VctPlayer>,
+    #[serde(rename = "roundResults")]
+    pub round_results: Vec<RoundWinner>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RoundWinner {
+    #[serde(rename = "winningTeam")]
+    pub winning_team: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MatchInfo {
     #[serde(rename = "matchId")]
@@ -80,8 +857,23 @@ pub struct MatchInfo {
     pub map: String,
     #[serde(rename = "gameStartMillis")]
     pub game_start_millis: i64,
-    #[serde(rename = "gameLengthMillis")]
-    pub game_length_millis: i64,
+    #[serde(rename = "gameLengthMillis", default)]
+    pub game_length_millis: Option<i64>,
+    /// Queue or game mode, e.g. "competitive"; absent from some exports
+    #[serde(rename = "queueId", default)]
+    pub queue_id: Option<String>,
+    #[serde(rename = "gameVersion", default)]
+    pub game_version: Option<String>,
+    /// Organization names per team; only some VCT exports include it
+    #[serde(rename = "teamData", default)]
+    pub team_data: Option<Vec<VctTeamData>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VctTeamData {
+    #[serde(rename = "teamId")]
+    pub team_id: String,
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -108,6 +900,14 @@ pub struct VctStats {
     pub rounds_played: Option<i32>,
 }
 
+/// Raw round data for one match, for views that need more than the parsed kill events
+#[derive(Debug)]
+pub struct MatchRounds {
+    pub map_display: String,
+    /// Empty for matches loaded from a normalized export, which keeps no raw rounds
+    pub round_results: Vec<RoundResult>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RoundResult {
     #[serde(rename = "roundNum")]
@@ -116,6 +916,20 @@ pub struct RoundResult {
     pub winning_team: Option<String>,
     #[serde(rename = "playerStats")]
     pub player_stats: Vec<PlayerRoundStats>,
+    #[serde(rename = "bombPlanter", default)]
+    pub bomb_planter: Option<String>,
+    #[serde(rename = "plantRoundTime", default)]
+    pub plant_round_time: Option<i32>,
+    #[serde(rename = "plantLocation", default)]
+    pub plant_location: Option<Location>,
+    #[serde(rename = "plantSite", default)]
+    pub plant_site: Option<String>,
+    #[serde(rename = "bombDefuser", default)]
+    pub bomb_defuser: Option<String>,
+    #[serde(rename = "defuseRoundTime", default)]
+    pub defuse_round_time: Option<i32>,
+    #[serde(rename = "defuseLocation", default)]
+    pub defuse_location: Option<Location>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -136,17 +950,23 @@ pub struct Kill {
     pub victim_location: Option<Location>,
     #[serde(rename = "timeSinceRoundStartMillis")]
     pub time_since_round_start_millis: i32,
+    #[serde(rename = "timeSinceGameStartMillis", default)]
+    pub time_since_game_start_millis: Option<i64>,
     #[serde(rename = "playerLocations")]
     pub player_locations: Vec<PlayerLocation>,
+    #[serde(default)]
+    pub assistants: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct FinishingDamage {
+    #[serde(rename = "damageType", default)]
+    pub damage_type: Option<String>,
     #[serde(rename = "damageItem")]
     pub damage_item: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerLocation {
     pub puuid: String,
     pub location: Location,
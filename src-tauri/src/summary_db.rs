@@ -0,0 +1,123 @@
+use crate::error::ProcessorError;
+use crate::models::IndexedSummary;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// SQLite cache of parsed match summaries keyed by file path and modification time
+pub struct SummaryDb {
+    conn: Connection,
+}
+
+impl SummaryDb {
+    /// Open (or create) the cache database at the given path
+    pub fn open(db_path: &Path) -> Result<Self, ProcessorError> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(db_path)
+            .map_err(|e| ProcessorError::Other(format!("Failed to open summary database: {}", e)))?;
+
+        // Archive files hold several matches, so rows are keyed by path and entry position
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS match_summaries (
+                path TEXT NOT NULL,
+                entry INTEGER NOT NULL,
+                mtime_millis INTEGER NOT NULL,
+                match_id TEXT NOT NULL,
+                summary_json TEXT NOT NULL,
+                PRIMARY KEY (path, entry)
+            );
+            CREATE INDEX IF NOT EXISTS match_summaries_match_id ON match_summaries (match_id);",
+        )
+        .map_err(|e| ProcessorError::Other(format!("Failed to initialize summary database: {}", e)))?;
+
+        Ok(SummaryDb { conn })
+    }
+
+    /// Load every file's cached summaries, in file order, along with the modification time they were parsed at
+    pub fn load_all(&self) -> Result<HashMap<PathBuf, (i64, Vec<IndexedSummary>)>, ProcessorError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, mtime_millis, summary_json FROM match_summaries ORDER BY path, entry")
+            .map_err(|e| ProcessorError::Other(format!("Failed to query summary database: {}", e)))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| ProcessorError::Other(format!("Failed to query summary database: {}", e)))?;
+
+        let mut cached: HashMap<PathBuf, (i64, Vec<IndexedSummary>)> = HashMap::new();
+        let mut stale = std::collections::HashSet::new();
+        for (path, mtime, summary_json) in rows.filter_map(|r| r.ok()) {
+            let path = PathBuf::from(path);
+            match serde_json::from_str::<IndexedSummary>(&summary_json) {
+                Ok(summary) => cached.entry(path).or_insert_with(|| (mtime, Vec::new())).1.push(summary),
+                // Rows written by an older summary layout make the whole file get reparsed
+                Err(_) => {
+                    stale.insert(path);
+                }
+            }
+        }
+
+        cached.retain(|path, _| !stale.contains(path));
+        Ok(cached)
+    }
+
+    /// Replace the cached summaries for the given files in one transaction
+    pub fn upsert_all(&mut self, entries: &[(PathBuf, i64, Vec<IndexedSummary>)]) -> Result<(), ProcessorError> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| ProcessorError::Other(format!("Failed to start transaction: {}", e)))?;
+
+        {
+            let mut delete = tx
+                .prepare("DELETE FROM match_summaries WHERE path = ?1")
+                .map_err(|e| ProcessorError::Other(format!("Failed to prepare upsert: {}", e)))?;
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO match_summaries
+                        (path, entry, mtime_millis, match_id, summary_json)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(|e| ProcessorError::Other(format!("Failed to prepare upsert: {}", e)))?;
+
+            for (path, mtime, summaries) in entries {
+                // A file that shrank must not leave its old trailing entries behind
+                delete
+                    .execute(params![path.to_string_lossy()])
+                    .map_err(|e| ProcessorError::Other(format!("Failed to upsert {}: {}", path.display(), e)))?;
+
+                for (entry, indexed) in summaries.iter().enumerate() {
+                    let summary_json = serde_json::to_string(indexed)?;
+
+                    insert.execute(params![
+                        path.to_string_lossy(),
+                        entry as i64,
+                        mtime,
+                        indexed.summary.match_id,
+                        summary_json,
+                    ])
+                    .map_err(|e| ProcessorError::Other(format!("Failed to upsert {}: {}", path.display(), e)))?;
+                }
+            }
+        }
+
+        tx.commit().map_err(|e| ProcessorError::Other(format!("Failed to commit summaries: {}", e)))
+    }
+
+    /// Remove every cached summary so the next load reparses all files
+    pub fn clear(&self) -> Result<(), ProcessorError> {
+        self.conn
+            .execute("DELETE FROM match_summaries", [])
+            .map(|_| ())
+            .map_err(|e| ProcessorError::Other(format!("Failed to clear summary database: {}", e)))
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::ProcessorError;
+use crate::models::{MatchSummary, MatchTag};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TAGS_FILE_NAME: &str = "match_tags.json";
+
+/// User-defined match labels, persisted as JSON in the app data directory
+pub struct TagStore {
+    path: PathBuf,
+    tags: HashMap<String, Vec<String>>,
+}
+
+impl TagStore {
+    /// Load the tags saved in `data_dir`, starting empty if the file is missing or corrupt
+    pub fn open(data_dir: &Path) -> Self {
+        let path = data_dir.join(TAGS_FILE_NAME);
+
+        let saved: Vec<MatchTag> = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                eprintln!("Ignoring corrupt match tags {}: {}", path.display(), e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        TagStore {
+            path,
+            tags: saved.into_iter().map(|tag| (tag.match_id, tag.labels)).collect(),
+        }
+    }
+
+    /// Label a match; adding a label it already has is a no-op
+    pub fn add_tag(&mut self, match_id: &str, label: &str) -> Result<(), ProcessorError> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(ProcessorError::Other("Tag label cannot be empty".to_string()));
+        }
+
+        let labels = self.tags.entry(match_id.to_string()).or_default();
+        if labels.iter().any(|l| l == label) {
+            return Ok(());
+        }
+        labels.push(label.to_string());
+        self.save()
+    }
+
+    /// Remove a label from a match; removing a label it doesn't have is a no-op
+    pub fn remove_tag(&mut self, match_id: &str, label: &str) -> Result<(), ProcessorError> {
+        let label = label.trim();
+        let Some(labels) = self.tags.get_mut(match_id) else {
+            return Ok(());
+        };
+
+        let before = labels.len();
+        labels.retain(|l| l != label);
+        if labels.len() == before {
+            return Ok(());
+        }
+        if labels.is_empty() {
+            self.tags.remove(match_id);
+        }
+        self.save()
+    }
+
+    /// Labels on a match, in the order they were added
+    pub fn get_tags(&self, match_id: &str) -> Vec<String> {
+        self.tags.get(match_id).cloned().unwrap_or_default()
+    }
+
+    /// The summaries whose match carries `label`
+    pub fn filter_by_tag(&self, summaries: &[MatchSummary], label: &str) -> Vec<MatchSummary> {
+        let label = label.trim();
+        summaries
+            .iter()
+            .filter(|m| self.tags.get(&m.match_id).is_some_and(|labels| labels.iter().any(|l| l == label)))
+            .cloned()
+            .collect()
+    }
+
+    fn save(&self) -> Result<(), ProcessorError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Sorted so the file diffs cleanly between saves
+        let mut saved: Vec<MatchTag> = self.tags
+            .iter()
+            .map(|(match_id, labels)| MatchTag {
+                match_id: match_id.clone(),
+                labels: labels.clone(),
+            })
+            .collect();
+        saved.sort_by(|a, b| a.match_id.cmp(&b.match_id));
+
+        let contents = serde_json::to_string_pretty(&saved)?;
+        Ok(fs::write(&self.path, contents)?)
+    }
+}
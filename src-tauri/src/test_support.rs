@@ -0,0 +1,157 @@
+//! Synthetic VCT match files shared by the unit tests
+
+use crate::json_processor;
+use crate::models::{KillEvent, MatchDetail, VctMatchData};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Held by tests that load folders or open matches, since the index and caches are process-wide
+static GLOBAL_STATE: Mutex<()> = Mutex::new(());
+
+pub fn lock_global_state() -> MutexGuard<'static, ()> {
+    GLOBAL_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub const VANDAL: &str = "9C82E19D-4575-0200-1A81-3EACF00CF872";
+pub const PHANTOM: &str = "EE8E8D15-496B-07AC-E5F6-8FAE5D4C7B1A";
+pub const OPERATOR: &str = "A03B24D3-4319-996D-0F8C-94BBFBA1DFC7";
+
+/// A player on `team`, named after their puuid
+pub fn player(puuid: &str, team: &str) -> Value {
+    json!({
+        "puuid": puuid,
+        "gameName": puuid.to_uppercase(),
+        "tagLine": "TEST",
+        "characterId": "569fdd95-4d10-43ab-ca70-79becc718b46",
+        "teamId": team,
+        "stats": { "score": 200, "kills": 0, "deaths": 0, "assists": 0, "roundsPlayed": 1 }
+    })
+}
+
+/// Five Blue players `b0`..`b4` and five Red players `r0`..`r4`
+pub fn ten_players() -> Vec<Value> {
+    (0..5)
+        .map(|i| player(&format!("b{}", i), "Blue"))
+        .chain((0..5).map(|i| player(&format!("r{}", i), "Red")))
+        .collect()
+}
+
+/// A Vandal kill with the victim at `victim_at` and no other positions recorded
+pub fn kill(killer: &str, victim: &str, round_time_millis: i32, victim_at: (i32, i32)) -> Value {
+    json!({
+        "killer": killer,
+        "victim": victim,
+        "finishingDamage": { "damageType": "Weapon", "damageItem": VANDAL },
+        "killerLocation": null,
+        "victimLocation": { "x": victim_at.0, "y": victim_at.1 },
+        "timeSinceRoundStartMillis": round_time_millis,
+        "playerLocations": [],
+        "assistants": []
+    })
+}
+
+/// Swap the finishing weapon of a [`kill`]
+pub fn with_weapon(mut kill: Value, damage_item: &str) -> Value {
+    kill["finishingDamage"]["damageItem"] = json!(damage_item);
+    kill
+}
+
+/// Record the killer of a [`kill`] standing at `killer_at`
+pub fn with_killer_at(mut kill: Value, killer_at: (i32, i32)) -> Value {
+    let killer = kill["killer"].clone();
+    kill["playerLocations"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "puuid": killer, "location": { "x": killer_at.0, "y": killer_at.1 } }));
+    kill
+}
+
+/// A round won by `winner`, with each kill filed under its killer's stats
+pub fn round(round_num: i32, winner: &str, kills: Vec<Value>) -> Value {
+    let mut player_stats: Vec<Value> = Vec::new();
+    for kill in kills {
+        let killer = kill["killer"].clone();
+        match player_stats.iter_mut().find(|stats| stats["puuid"] == killer) {
+            Some(stats) => stats["kills"].as_array_mut().unwrap().push(kill),
+            None => player_stats.push(json!({ "puuid": killer, "kills": [kill] })),
+        }
+    }
+
+    json!({
+        "roundNum": round_num,
+        "winningTeam": winner,
+        "playerStats": player_stats
+    })
+}
+
+/// A round where `b4` is left alone against `opponents` Red players, then wins or dies
+///
+/// Blue trades down to 5 - `opponents` Red players before `r4` kills `b0`..`b3`.
+pub fn clutch_round(round_num: i32, opponents: usize, won: bool) -> Value {
+    let mut kills = Vec::new();
+    for i in 0..5 - opponents {
+        kills.push(kill("b0", &format!("r{}", i), 1_000 + i as i32 * 100, (1000, -3000)));
+    }
+    for i in 0..4 {
+        kills.push(kill("r4", &format!("b{}", i), 5_000 + i * 1_000, (1000, -3000)));
+    }
+    if won {
+        for i in 5 - opponents..5 {
+            kills.push(kill("b4", &format!("r{}", i), 20_000 + i as i32 * 1_000, (1000, -3000)));
+        }
+    } else {
+        kills.push(kill("r4", "b4", 20_000, (1000, -3000)));
+    }
+
+    round(round_num, if won { "Blue" } else { "Red" }, kills)
+}
+
+/// A match on Ascent started at 2023-11-14 22:13:20 UTC
+pub fn vct_match(match_id: &str, players: Vec<Value>, rounds: Vec<Value>) -> Value {
+    json!({
+        "matchInfo": {
+            "matchId": match_id,
+            "map": "/Game/Maps/Ascent/Ascent",
+            "gameStartMillis": 1_700_000_000_000i64,
+            "gameLengthMillis": 2_400_000,
+            "gameVersion": "release-08.11-shipping-9-2444158"
+        },
+        "players": players,
+        "roundResults": rounds
+    })
+}
+
+/// A one-round match between [`ten_players`] with a single kill
+pub fn simple_match(match_id: &str) -> Value {
+    vct_match(match_id, ten_players(), vec![round(0, "Blue", vec![kill("b0", "r0", 10_000, (1000, -3000))])])
+}
+
+/// Deserialize a match built with [`vct_match`]
+pub fn match_data(value: &Value) -> VctMatchData {
+    serde_json::from_value(value.clone()).unwrap()
+}
+
+/// Parse a match built with [`vct_match`] as if it were read from `test.json`
+pub fn parse_detail(value: &Value) -> MatchDetail {
+    json_processor::parse_match_detail(Path::new("test.json"), &match_data(value))
+}
+
+/// Kill events of a match between [`ten_players`] made of `rounds`
+pub fn kill_events(rounds: Vec<Value>) -> Vec<KillEvent> {
+    parse_detail(&vct_match("test", ten_players(), rounds)).kill_events
+}
+
+pub fn write_match(dir: &Path, file_name: &str, value: &Value) -> PathBuf {
+    let path = dir.join(file_name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(&path, value.to_string()).unwrap();
+    path
+}
+
+pub fn path_str(dir: &Path) -> &str {
+    dir.to_str().unwrap()
+}
@@ -0,0 +1,7 @@
+/// Render a duration as minutes and seconds, e.g. "42m 17s"
+///
+/// Negative durations are shown as "0m 0s".
+pub fn format_game_duration(millis: i64) -> String {
+    let total_seconds = millis.max(0) / 1000;
+    format!("{}m {}s", total_seconds / 60, total_seconds % 60)
+}
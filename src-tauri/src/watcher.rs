@@ -0,0 +1,130 @@
+use crate::error::ProcessorError;
+use crate::json_processor;
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Active folder watcher, dropped to stop watching
+static FOLDER_WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// How long a file must go without new events before it is parsed
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Parse attempts for a file that keeps failing, e.g. because it is still being copied
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Whether an event can mean a match file appeared or finished being written
+///
+/// Files are often created empty and filled in afterwards, and files moved into
+/// the folder only show up as renames, so creation alone isn't enough.
+fn is_file_update(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Name(_) | ModifyKind::Any)
+    )
+}
+
+/// Watch a folder for new match files and emit a "new-match" event for each
+///
+/// Events are debounced per file, and a file that fails to parse is retried a
+/// few times before it is given up on.
+pub fn watch_folder(folder_path: &str, app: AppHandle) -> Result<(), ProcessorError> {
+    let path = Path::new(folder_path);
+
+    if !path.exists() {
+        return Err(ProcessorError::FolderNotFound(folder_path.to_string()));
+    }
+
+    let (sender, receiver) = mpsc::channel::<PathBuf>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let event = match res {
+            Ok(event) => event,
+            Err(e) => {
+                eprintln!("Folder watch error: {}", e);
+                return;
+            }
+        };
+
+        if !is_file_update(&event.kind) {
+            return;
+        }
+
+        // Renames report the old path too, which no longer exists
+        for file_path in event.paths {
+            if json_processor::is_match_file(&file_path) && file_path.is_file() {
+                let _ = sender.send(file_path);
+            }
+        }
+    })
+    .map_err(|e| ProcessorError::Other(format!("Failed to create folder watcher: {}", e)))?;
+
+    watcher
+        .watch(path, RecursiveMode::Recursive)
+        .map_err(|e| ProcessorError::Other(format!("Failed to watch {}: {}", folder_path, e)))?;
+
+    // The worker exits once the watcher, and with it the sender, is dropped
+    thread::spawn(move || index_changed_files(receiver, app));
+
+    // Replacing the previous watcher drops it, so only one folder is watched at a time
+    *FOLDER_WATCHER.lock().unwrap() = Some(watcher);
+
+    Ok(())
+}
+
+/// Index files once they have been quiet for [`DEBOUNCE`], retrying ones that fail to parse
+fn index_changed_files(receiver: Receiver<PathBuf>, app: AppHandle) {
+    // Path → (time of the last event or attempt, attempts so far)
+    let mut pending: HashMap<PathBuf, (Instant, u32)> = HashMap::new();
+
+    loop {
+        match receiver.recv_timeout(DEBOUNCE) {
+            Ok(file_path) => {
+                pending.entry(file_path).or_insert((Instant::now(), 0)).0 = Instant::now();
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) if pending.is_empty() => return,
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(DEBOUNCE),
+        }
+
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, (last_seen, _))| last_seen.elapsed() >= DEBOUNCE)
+            .map(|(file_path, _)| file_path.clone())
+            .collect();
+
+        for file_path in ready {
+            match json_processor::index_new_file(&file_path) {
+                Ok(summaries) => {
+                    pending.remove(&file_path);
+                    for summary in summaries {
+                        if let Err(e) = app.emit("new-match", &summary) {
+                            eprintln!("Failed to emit new-match event: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    let (last_seen, attempts) = pending.get_mut(&file_path).unwrap();
+                    *attempts += 1;
+                    *last_seen = Instant::now();
+                    if *attempts >= MAX_ATTEMPTS || !file_path.is_file() {
+                        eprintln!("{}", e);
+                        pending.remove(&file_path);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Stop watching the current folder, if any
+pub fn unwatch_folder() {
+    FOLDER_WATCHER.lock().unwrap().take();
+}
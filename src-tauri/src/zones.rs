@@ -0,0 +1,108 @@
+use crate::error::ProcessorError;
+use crate::json_processor;
+use crate::models::*;
+use std::collections::HashMap;
+
+/// Zone polygons bundled with the app, keyed by map display name
+///
+/// Coordinates are raw game units. The shipped Ascent zones are coarse
+/// B / Mid / A bands; edit resources/map_zones.json to add finer callouts.
+const BUNDLED_MAP_ZONES: &str = include_str!("../resources/map_zones.json");
+
+/// Key used for kills that fall outside every zone
+pub const OTHER_ZONE: &str = "Other";
+
+/// Look up the bundled zones for a map, if any are defined
+pub fn bundled_map_zones(map_display: &str) -> Result<Option<MapZones>, ProcessorError> {
+    let mut all: HashMap<String, Vec<MapZone>> = serde_json::from_str(BUNDLED_MAP_ZONES)?;
+
+    Ok(all.remove(map_display).map(|zones| MapZones {
+        map: map_display.to_string(),
+        zones,
+    }))
+}
+
+/// Even-odd ray casting test; points exactly on an edge may land on either side
+pub fn point_in_polygon(point: &Location, polygon: &[Location]) -> bool {
+    let (px, py) = (point.x as f64, point.y as f64);
+    let mut inside = false;
+
+    for (i, a) in polygon.iter().enumerate() {
+        let b = &polygon[(i + polygon.len() - 1) % polygon.len()];
+        let (ax, ay, bx, by) = (a.x as f64, a.y as f64, b.x as f64, b.y as f64);
+
+        if (ay > py) != (by > py) && px < (bx - ax) * (py - ay) / (by - ay) + ax {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Count a match's kills per zone from the given perspective
+///
+/// Every zone is present in the result, even with zero kills. Kills outside all
+/// zones are counted under [`OTHER_ZONE`]; kills without a usable position are skipped.
+pub fn classify_kill_zones(round_results: &[RoundResult], zones: &MapZones, perspective: Perspective) -> HashMap<String, u32> {
+    let (kills, _) = json_processor::extract_kills(round_results, &HashMap::new());
+    let mut counts: HashMap<String, u32> = zones.zones
+        .iter()
+        .map(|zone| (zone.name.clone(), 0))
+        .collect();
+
+    for location in kills.iter().flat_map(|(kill, event)| json_processor::perspective_locations(kill, event, perspective)) {
+        // Same placeholder values the minimap transform rejects
+        if location.x == 0 || location.y == 0 || location.x == -999 || location.y == -999 {
+            continue;
+        }
+
+        let zone = zones.zones
+            .iter()
+            .find(|zone| point_in_polygon(location, &zone.polygon))
+            .map_or(OTHER_ZONE, |zone| zone.name.as_str());
+
+        *counts.entry(zone.to_string()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    fn at(x: i32, y: i32) -> Location {
+        Location { x, y }
+    }
+
+    #[test]
+    fn point_in_polygon_handles_inside_and_outside() {
+        // L-shaped polygon, so the notch is inside the bounding box but outside the shape
+        let polygon = vec![at(0, 0), at(10, 0), at(10, 5), at(5, 5), at(5, 10), at(0, 10)];
+
+        assert!(point_in_polygon(&at(2, 2), &polygon));
+        assert!(point_in_polygon(&at(2, 8), &polygon));
+        assert!(!point_in_polygon(&at(8, 8), &polygon));
+        assert!(!point_in_polygon(&at(-1, 5), &polygon));
+    }
+
+    #[test]
+    fn deaths_are_counted_per_bundled_zone() {
+        let zones = bundled_map_zones("Ascent").unwrap().unwrap();
+        let data = match_data(&vct_match("zones", ten_players(), vec![round(0, "Blue", vec![
+            kill("b0", "r0", 1_000, (1000, -8000)),
+            kill("b0", "r1", 2_000, (1000, -4000)),
+            kill("b0", "r2", 3_000, (1000, -1000)),
+            kill("b0", "r3", 4_000, (1000, 5000)),
+        ])]));
+
+        let counts = classify_kill_zones(&data.round_results, &zones, Perspective::Deaths);
+
+        assert_eq!(counts["B Site"], 1);
+        assert_eq!(counts["Mid"], 1);
+        assert_eq!(counts["A Site"], 1);
+        assert_eq!(counts[OTHER_ZONE], 1);
+        assert!(bundled_map_zones("Nowhere").unwrap().is_none());
+    }
+}
@@ -0,0 +1,23 @@
+//! Smoke test against a real VCT data folder, which is never committed
+//!
+//! Set `SOUP_TEST_DATA_DIR` to a folder of VCT match exports to run it;
+//! without it the test only prints a note and passes.
+
+use soupheatmap::json_processor::{get_match_by_id, load_json_files};
+use soupheatmap::models::LoadOptions;
+
+#[test]
+fn loads_real_vct_data() {
+    let Ok(data_dir) = std::env::var("SOUP_TEST_DATA_DIR") else {
+        eprintln!("SOUP_TEST_DATA_DIR is not set; skipping the real data smoke test");
+        return;
+    };
+
+    let result = load_json_files(&data_dir, None, &LoadOptions::default()).unwrap();
+    assert!(!result.summaries.is_empty(), "no matches loaded from {}", data_dir);
+
+    let first = &result.summaries[0];
+    let detail = get_match_by_id(Some(&data_dir), &first.match_id).unwrap();
+    assert!(!detail.kill_events.is_empty(), "match {} has no kill events", first.match_id);
+    assert!(detail.players.len() >= 10, "match {} has only {} players", first.match_id, detail.players.len());
+}
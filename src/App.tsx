@@ -1,6 +1,6 @@
 import { useState, useMemo, useEffect } from 'react'
 import './App.css'
-import { selectFolder, loadMatchesWithProgress, getMatchDetail, getMultipleMatchDetailsWithProgress } from './lib/tauri-api'
+import { selectFolder, loadMatchesWithProgress, getMatchDetail, getMultipleMatchDetailsWithProgress, errorMessage } from './lib/tauri-api'
 import { saveImageFile } from './lib/export-utils'
 import type { MatchSummary, MatchDetail } from './types'
 import { Heatmap } from './components/Heatmap'
@@ -108,7 +108,7 @@ function App() {
       setMatches(loadedMatches)
       setLoadingProgress(null)
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(errorMessage(err))
       setLoadingProgress(null)
     } finally {
       setLoading(false)
@@ -147,7 +147,7 @@ function App() {
       setTimeRange([0, 150])
 
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(errorMessage(err))
     } finally {
       setLoadingMatchDetails(false)
     }
@@ -242,7 +242,7 @@ function App() {
         }
       }, 100) // Small delay to allow UI to update first
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(errorMessage(err))
     } finally {
       setLoading(false)
       setLoadingProgress(null)
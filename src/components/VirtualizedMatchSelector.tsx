@@ -189,7 +189,9 @@ export const VirtualizedMatchSelector: React.FC<VirtualizedMatchSelectorProps> =
                   <span className="match-region">{match.region}</span>
                 </div>
                 <div className="match-date">
-                  {new Date(match.game_start).toLocaleDateString()} {new Date(match.game_start).toLocaleTimeString([], {hour: '2-digit', minute:'2-digit'})}
+                  {match.game_start
+                    ? `${new Date(match.game_start).toLocaleDateString()} ${new Date(match.game_start).toLocaleTimeString([], {hour: '2-digit', minute:'2-digit'})}`
+                    : 'Unknown date'}
                 </div>
               </div>
             );
@@ -3,7 +3,23 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { MatchSummary, MatchDetail } from '../types';
+import type { MatchSummary, MatchDetail, LoadResult, ProcessorError } from '../types';
+
+/**
+ * Check whether a rejected invoke carries a structured backend error
+ */
+export function isProcessorError(error: unknown): error is ProcessorError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'message' in error;
+}
+
+/**
+ * Human-readable message for any error thrown by a backend call
+ */
+export function errorMessage(error: unknown): string {
+  if (isProcessorError(error)) return error.message;
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
 
 /**
  * Open folder selection dialog and return selected path
@@ -23,8 +39,8 @@ export async function selectFolder(): Promise<string> {
  */
 export async function loadMatches(folderPath: string): Promise<MatchSummary[]> {
   try {
-    const result = await invoke<MatchSummary[]>('load_matches', { folderPath });
-    return result;
+    const result = await invoke<LoadResult>('load_matches', { folderPath });
+    return result.summaries;
   } catch (error) {
     console.error('Error loading matches:', error);
     throw error;
@@ -45,13 +61,19 @@ export async function loadMatchesWithProgress(
       onProgress({ processed: 0, total: 100, percentage: 0 });
     }
 
-    const result = await invoke<MatchSummary[]>('load_matches_with_progress', { folderPath });
+    const result = await invoke<LoadResult>('load_matches_with_progress', { folderPath });
 
     if (onProgress) {
       onProgress({ processed: 100, total: 100, percentage: 100 });
     }
 
-    return result;
+    if (result.duplicates_skipped > 0 || result.parse_errors > 0) {
+      console.warn(
+        `Skipped ${result.duplicates_skipped} duplicate and ${result.parse_errors} unreadable match files`
+      );
+    }
+
+    return result.summaries;
   } catch (error) {
     console.error('Error loading matches:', error);
     throw error;
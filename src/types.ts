@@ -8,10 +8,41 @@ export interface Location {
 export interface MatchSummary {
   match_id: string;
   map: string;
+  map_display: string;
   region: string;
-  game_start: string;
+  game_start: string | null;
+  game_start_valid: boolean;
   teams: string[];
+  team_sizes: Record<string, number>;
   score: string;
+  rounds_counted: number;
+  game_length_millis: number | null;
+  game_length_display: string | null;
+  queue: string | null;
+  game_version: string | null;
+  is_valid_match: boolean;
+}
+
+export type ProcessorErrorKind =
+  | 'FolderNotFound'
+  | 'FileRead'
+  | 'Parse'
+  | 'MatchNotFound'
+  | 'InvalidData'
+  | 'Cancelled'
+  | 'Other';
+
+export interface ProcessorError {
+  kind: ProcessorErrorKind;
+  message: string;
+}
+
+export interface LoadResult {
+  summaries: MatchSummary[];
+  duplicates_skipped: number;
+  parse_errors: number;
+  warnings: string[];
+  no_matches_found: boolean;
 }
 
 export interface PlayerStats {
@@ -29,26 +60,63 @@ export interface PlayerStats {
   is_observer: boolean;
 }
 
+export type RoundPhase = 'FirstHalf' | 'SecondHalf' | 'Overtime';
+
+export type RoundType = 'Execute' | 'Retake' | 'NoPlant';
+
+export type KillCategory = 'Gun' | 'Ability' | 'Melee' | 'Bomb' | 'Fall' | 'Unknown';
+
 export interface KillEvent {
   killer_puuid: string;
   victim_puuid: string;
   weapon: string | null;
-  killer_location: Location;
+  kill_category: KillCategory;
+  killer_location: Location | null;
   victim_location: Location;
+  distance: number;
+  distance_reliable: boolean;
+  assistants: string[];
+  round_won_by_killer: boolean | null;
   round_num: number;
+  round_phase: RoundPhase;
   round_time_millis: number;
+  game_time_millis: number | null;
 }
 
 export interface MatchDetail {
   match_id: string;
   map: string;
+  map_display: string;
   region: string;
-  game_start: string;
-  game_length_millis: number;
+  game_start: string | null;
+  game_start_valid: boolean;
+  game_length_millis: number | null;
+  game_length_formatted: string;
   rounds_played: number;
+  overtime_rounds: number;
   winning_team: string;
   players: PlayerStats[];
+  mvp_puuid: string | null;
   kill_events: KillEvent[];
+  dropped_kill_events: number;
+  rounds: RoundOutcome[];
+  team_display_names: Record<string, string>;
+  round_types: RoundType[];
+}
+
+export interface RoundOutcome {
+  round_num: number;
+  winning_team: string | null;
+  round_phase: RoundPhase;
+  plant: SpikeEvent | null;
+  defuse: SpikeEvent | null;
+}
+
+export interface SpikeEvent {
+  player_puuid: string;
+  round_time_millis: number;
+  location: Location | null;
+  site: string | null;
 }
 
 // Player map for tooltips